@@ -0,0 +1,232 @@
+use crate::{
+    cli_args::{BlockChain, Scout},
+    run::analyze_project,
+    scout::{
+        core::suppression::SuppressedFinding,
+        output::report::{Report, Severity},
+    },
+    util::detectors_info::LintStore,
+};
+use anyhow::Result;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Runs scout from another program and returns the typed results.
+///
+/// ```no_run
+/// use cargo_scout_audit::{ScoutBuilder, scout::output::report::Severity};
+///
+/// let result = ScoutBuilder::new()
+///     .manifest_path("contracts/token/Cargo.toml")
+///     .fail_on(Severity::Medium)
+///     .run()?;
+/// for finding in result.report.findings.iter() {
+///     println!("{} at {}", finding.vulnerability_id, finding.span);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// Nothing is printed and no report is written or opened. The findings cache
+/// in the target directory is still used, unless disabled with `no_cache`.
+#[derive(Debug, Default, Clone)]
+pub struct ScoutBuilder {
+    opts: Scout,
+}
+
+impl ScoutBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Cargo.toml` of the package or workspace to analyze. Defaults to the
+    /// current directory.
+    pub fn manifest_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.opts.manifest_path = Some(path.into());
+        self
+    }
+
+    /// Analyzes every package as this blockchain instead of detecting it.
+    pub fn blockchain(mut self, blockchain: BlockChain) -> Self {
+        self.opts.blockchain = Some(blockchain);
+        self
+    }
+
+    /// Only analyzes these workspace members.
+    pub fn packages<S: Into<String>>(mut self, packages: impl IntoIterator<Item = S>) -> Self {
+        self.opts.package = packages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Leaves these workspace members out.
+    pub fn exclude_packages<S: Into<String>>(
+        mut self,
+        packages: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.opts.exclude_package = packages.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Only runs these detectors (`unsafe-unwrap`).
+    pub fn detectors<S: Into<String>>(mut self, detectors: impl IntoIterator<Item = S>) -> Self {
+        self.opts.filter = Some(join(detectors));
+        self.opts.exclude = None;
+        self
+    }
+
+    /// Runs every detector but these.
+    pub fn exclude_detectors<S: Into<String>>(
+        mut self,
+        detectors: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.opts.exclude = Some(join(detectors));
+        self.opts.filter = None;
+        self
+    }
+
+    /// Only reports findings in files matching these globs.
+    pub fn include_paths<S: Into<String>>(mut self, globs: impl IntoIterator<Item = S>) -> Self {
+        self.opts.include_paths = globs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Ignores findings in files matching these globs.
+    pub fn exclude_paths<S: Into<String>>(mut self, globs: impl IntoIterator<Item = S>) -> Self {
+        self.opts.exclude_paths = globs.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets `AnalysisResult::threshold_exceeded` when a finding reaches this
+    /// severity.
+    pub fn fail_on(mut self, severity: Severity) -> Self {
+        self.opts.fail_on = Some(severity);
+        self
+    }
+
+    /// Only reports findings missing from this baseline file.
+    pub fn baseline(mut self, path: impl Into<PathBuf>) -> Self {
+        self.opts.baseline = Some(path.into());
+        self
+    }
+
+    /// Only reports findings on lines changed since this git reference.
+    pub fn diff_base(mut self, git_ref: impl Into<String>) -> Self {
+        self.opts.diff_base = Some(git_ref.into());
+        self
+    }
+
+    /// Uses the detectors of a local checkout instead of downloading them.
+    pub fn local_detectors(mut self, path: impl Into<PathBuf>) -> Self {
+        self.opts.local_detectors = Some(path.into());
+        self
+    }
+
+    /// Builds the drivers from a local clone of the scout repository.
+    pub fn scout_source(mut self, path: impl Into<PathBuf>) -> Self {
+        self.opts.scout_source = Some(path.into());
+        self
+    }
+
+    /// Uses the detectors of an imported bundle, without network access.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.opts.offline = offline;
+        self
+    }
+
+    pub fn no_cache(mut self, no_cache: bool) -> Self {
+        self.opts.no_cache = no_cache;
+        self
+    }
+
+    pub fn no_telemetry(mut self, no_telemetry: bool) -> Self {
+        self.opts.no_telemetry = no_telemetry;
+        self
+    }
+
+    /// Builds the project in debug mode instead of release.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.opts.debug = debug;
+        self
+    }
+
+    /// Extra arguments for `cargo check`.
+    pub fn cargo_args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.opts.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn run(self) -> Result<AnalysisResult> {
+        let mut opts = self.opts;
+        opts.quiet = true;
+        analyze_project(opts)
+    }
+}
+
+fn join<S: Into<String>>(values: impl IntoIterator<Item = S>) -> String {
+    values
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// What `ScoutBuilder::run` found.
+#[derive(Debug)]
+pub struct AnalysisResult {
+    /// Same contents as the JSON report, with the triage statuses applied.
+    pub report: Report,
+    /// Metadata of the detectors that ran, after the severity overrides.
+    pub detectors_info: LintStore,
+    /// Whether each analyzed crate built, keyed by crate name.
+    pub crates: HashMap<String, bool>,
+    /// Set when `fail_on` was given and an untriaged finding reaches it.
+    pub threshold_exceeded: bool,
+}
+
+impl AnalysisResult {
+    /// Findings silenced with `#[scout_allow]`.
+    pub fn suppressed_findings(&self) -> &[SuppressedFinding] {
+        &self.report.suppressed_findings
+    }
+
+    /// Crates that failed to build, so their findings may be incomplete.
+    pub fn failed_crates(&self) -> Vec<&str> {
+        let mut crates = self
+            .crates
+            .iter()
+            .filter(|(_, success)| !**success)
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        crates.sort();
+        crates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScoutBuilder;
+    use crate::cli_args::BlockChain;
+
+    #[test]
+    fn builds_options() {
+        let builder = ScoutBuilder::new()
+            .manifest_path("contracts/Cargo.toml")
+            .blockchain(BlockChain::Soroban)
+            .packages(["token", "vault"])
+            .exclude_detectors(["unsafe-unwrap"])
+            .detectors(["overflow-check", "divide-before-multiply"])
+            .cargo_args(["--features", "testutils"]);
+
+        let opts = builder.opts;
+        assert_eq!(
+            opts.manifest_path.unwrap().to_str(),
+            Some("contracts/Cargo.toml")
+        );
+        assert_eq!(opts.package, ["token", "vault"]);
+        assert_eq!(
+            opts.filter.as_deref(),
+            Some("overflow-check,divide-before-multiply")
+        );
+        assert!(opts.exclude.is_none());
+        assert_eq!(opts.args, ["--features", "testutils"]);
+        assert!(opts.output_format.is_empty() && !opts.tui && opts.events.is_none());
+    }
+}
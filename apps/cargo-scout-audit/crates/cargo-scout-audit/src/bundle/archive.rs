@@ -0,0 +1,30 @@
+use anyhow::{Context, Result};
+use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+use std::{fs::File, path::Path};
+
+// Packs the contents of a directory into a `.tar.gz` archive.
+pub fn pack(source: &Path, archive: &Path) -> Result<()> {
+    let file = File::create(archive)
+        .with_context(|| format!("Failed to create bundle archive at {}", archive.display()))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    builder
+        .append_dir_all(".", source)
+        .with_context(|| "Failed to add files to the bundle archive")?;
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .with_context(|| "Failed to write the bundle archive")?;
+    Ok(())
+}
+
+// Unpacks an archive created by `pack`. Entries that would land outside of
+// `destination` are skipped by `tar`.
+pub fn unpack(archive: &Path, destination: &Path) -> Result<()> {
+    let file = File::open(archive)
+        .with_context(|| format!("Failed to open bundle archive at {}", archive.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    archive.set_preserve_permissions(true);
+    archive
+        .unpack(destination)
+        .with_context(|| "Failed to extract the bundle archive")
+}
@@ -0,0 +1,134 @@
+use super::{
+    BIN_DIR, BundleManifest, BundledDetector, DETECTORS_DIR, LINTS_FILE, MANIFEST_FILE, PDF_DIR,
+    archive, detector_helper_file, pdf_generator_file, scout_driver_file,
+};
+use crate::{
+    cli_args::{BlockChain, Scout},
+    detector_helper::{build_detector_helper, get_detectors_info},
+    result::ScoutError,
+    scout::{
+        core::project_info::Project,
+        detectors::{builder::DetectorBuilder, configuration::DetectorsConfiguration},
+        output::pdf::external,
+    },
+    scout_driver::build_scout_driver,
+    util::{logger::TracedError, print::print_info},
+};
+use anyhow::{Context, Result};
+use cargo::{GlobalContext, core::Verbosity};
+use std::{fs, path::Path};
+use tempfile::TempDir;
+
+// Builds everything an offline run needs for the given blockchains, using the
+// toolchain of the project, and packs it into `path`.
+pub fn export_bundle(
+    opts: &Scout,
+    path: &Path,
+    blockchains: &[BlockChain],
+    with_pdf: bool,
+) -> Result<()> {
+    let metadata = Project::get_metadata(&opts.manifest_path)
+        .with_context(|| "Failed to get project metadata")?;
+    let blockchains = if blockchains.is_empty() {
+        let project_info = Project::get_info(&metadata, &opts.package_scope())
+            .map_err(ScoutError::GetProjectInfoFailed)?;
+        BlockChain::get_package_blockchains(&metadata, &project_info.packages, None)
+            .chains
+            .into_keys()
+            .collect()
+    } else {
+        blockchains.to_vec()
+    };
+    let toolchain = blockchains[0].get_toolchain(&metadata)?;
+
+    let cargo_config = GlobalContext::default().map_err(ScoutError::CargoConfigFailed.traced())?;
+    cargo_config.shell().set_verbosity(if opts.verbose {
+        Verbosity::Verbose
+    } else {
+        Verbosity::Quiet
+    });
+
+    let staging = TempDir::new()?;
+    let mut manifest = BundleManifest::new(&toolchain);
+
+    let helper_path = build_detector_helper(&toolchain, opts.scout_source.as_ref())?;
+
+    for blockchain in blockchains {
+        let detectors_config =
+            DetectorsConfiguration::get(blockchain, &toolchain, &opts.local_detectors, &metadata)
+                .map_err(ScoutError::DetectorsConfigFailed)?;
+        let detector_builder = DetectorBuilder::new(
+            &cargo_config,
+            &detectors_config,
+            &metadata,
+            opts.verbose,
+            &toolchain,
+        );
+        let detectors_names = detector_builder
+            .get_detector_names()
+            .map_err(ScoutError::GetDetectorNamesFailed)?;
+        let detectors_paths = detector_builder
+            .build(&detectors_names)
+            .map_err(ScoutError::BuildDetectorsFailed)?;
+
+        let directory = staging
+            .path()
+            .join(DETECTORS_DIR)
+            .join(blockchain.to_string());
+        fs::create_dir_all(&directory)?;
+
+        let mut detectors = Vec::new();
+        for detector_path in &detectors_paths {
+            let file = detector_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .with_context(|| format!("Invalid detector path {}", detector_path.display()))?;
+            fs::copy(detector_path, directory.join(file))
+                .with_context(|| format!("Failed to copy {}", detector_path.display()))?;
+            detectors.push(BundledDetector {
+                name: DetectorBuilder::normalize_detector_name(file),
+                file: file.to_string(),
+            });
+        }
+
+        let lints = get_detectors_info(&toolchain, &helper_path, &detectors_paths)?;
+        fs::write(
+            directory.join(LINTS_FILE),
+            serde_json::to_string_pretty(&lints)?,
+        )?;
+
+        manifest.detectors.insert(blockchain.to_string(), detectors);
+    }
+
+    let bin_directory = staging.path().join(BIN_DIR);
+    fs::create_dir_all(&bin_directory)?;
+    let driver_path = build_scout_driver(&toolchain, opts.scout_source.as_ref())?;
+    fs::copy(&driver_path, bin_directory.join(scout_driver_file()))
+        .with_context(|| "Failed to copy scout-driver")?;
+    fs::copy(&helper_path, bin_directory.join(detector_helper_file()))
+        .with_context(|| "Failed to copy detector-helper")?;
+
+    if with_pdf {
+        let pdf_directory = staging.path().join(PDF_DIR);
+        fs::create_dir_all(&pdf_directory)?;
+        fs::copy(
+            external::build_library(false)?,
+            pdf_directory.join(pdf_generator_file()),
+        )
+        .with_context(|| "Failed to copy the PDF generator")?;
+        manifest.pdf_generator = true;
+    }
+
+    fs::write(
+        staging.path().join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest)?,
+    )?;
+    archive::pack(staging.path(), path)?;
+
+    print_info(&format!(
+        "Exported detector bundle for {} to {}",
+        toolchain,
+        path.display()
+    ));
+    Ok(())
+}
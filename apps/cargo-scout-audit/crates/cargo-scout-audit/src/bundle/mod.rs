@@ -0,0 +1,375 @@
+mod archive;
+mod export;
+
+pub use export::export_bundle;
+
+use crate::{
+    cli_args::BlockChain,
+    detector_helper::get_detectors_info,
+    util::{detectors_info::LintStore, home::get_config_directory, print::print_info},
+};
+use anyhow::{Context, Result, bail};
+use current_platform::CURRENT_PLATFORM;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env::consts,
+    fs,
+    path::{Path, PathBuf},
+};
+use tempfile::TempDir;
+use thiserror::Error;
+
+const BUNDLE_VERSION: u32 = 1;
+const SCOUT_VERSION: &str = env!("CARGO_PKG_VERSION");
+const MANIFEST_FILE: &str = "manifest.json";
+const LINTS_FILE: &str = "lints.json";
+const DETECTORS_DIR: &str = "detectors";
+const BIN_DIR: &str = "bin";
+const PDF_DIR: &str = "pdf";
+
+#[derive(Error, Debug)]
+pub enum BundleError {
+    #[error(
+        "No detector bundle installed for {toolchain} ({platform}). Import one with `cargo scout-audit bundle import <PATH>`\n     → Expected at: {path}"
+    )]
+    NotInstalled {
+        toolchain: String,
+        platform: String,
+        path: PathBuf,
+    },
+
+    #[error("Unsupported bundle format version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("The bundle was exported by Scout {found}, but this is Scout {expected}")]
+    ScoutVersionMismatch { expected: String, found: String },
+
+    #[error("The bundle was exported for {found}, but this host is {expected}")]
+    PlatformMismatch { expected: String, found: String },
+
+    #[error("The bundle does not include detectors for {0}")]
+    MissingBlockchain(BlockChain),
+
+    #[error("The bundle does not include the PDF generator. Export it with `--with-pdf`")]
+    MissingPdfGenerator,
+
+    #[error(
+        "The detectors of the bundle could not be loaded with {0}. Is the toolchain installed?"
+    )]
+    VerificationFailed(String),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BundledDetector {
+    pub name: String,
+    pub file: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BundleManifest {
+    pub version: u32,
+    pub scout_version: String,
+    pub toolchain: String,
+    pub platform: String,
+    // Keyed by blockchain (`soroban`)
+    pub detectors: BTreeMap<String, Vec<BundledDetector>>,
+    pub pdf_generator: bool,
+}
+
+impl BundleManifest {
+    pub fn new(toolchain: &str) -> Self {
+        BundleManifest {
+            version: BUNDLE_VERSION,
+            scout_version: SCOUT_VERSION.to_string(),
+            toolchain: toolchain.to_string(),
+            platform: CURRENT_PLATFORM.to_string(),
+            detectors: BTreeMap::new(),
+            pdf_generator: false,
+        }
+    }
+}
+
+// Prebuilt detectors, drivers and detector metadata for one toolchain, so
+// that Scout can run without network access.
+//
+// Layout, both inside the archive and once installed:
+//   manifest.json
+//   bin/scout-driver, bin/detector-helper
+//   detectors/<blockchain>/<library>, detectors/<blockchain>/lints.json
+//   pdf/<library> (optional)
+#[derive(Debug)]
+pub struct DetectorBundle {
+    root: PathBuf,
+    manifest: BundleManifest,
+}
+
+impl DetectorBundle {
+    pub fn open(root: &Path) -> Result<Self> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        let manifest = fs::read_to_string(&manifest_path)
+            .with_context(|| format!("Failed to read {}", manifest_path.display()))?;
+        let manifest: BundleManifest = serde_json::from_str(&manifest)
+            .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+
+        if manifest.version != BUNDLE_VERSION {
+            bail!(BundleError::UnsupportedVersion(manifest.version));
+        }
+        if manifest.scout_version != SCOUT_VERSION {
+            bail!(BundleError::ScoutVersionMismatch {
+                expected: SCOUT_VERSION.to_string(),
+                found: manifest.scout_version,
+            });
+        }
+        if manifest.platform != CURRENT_PLATFORM {
+            bail!(BundleError::PlatformMismatch {
+                expected: CURRENT_PLATFORM.to_string(),
+                found: manifest.platform,
+            });
+        }
+
+        Ok(DetectorBundle {
+            root: root.to_path_buf(),
+            manifest,
+        })
+    }
+
+    pub fn open_installed(toolchain: &str) -> Result<Self> {
+        let path = Self::installed_path(toolchain);
+        if !path.join(MANIFEST_FILE).exists() {
+            bail!(BundleError::NotInstalled {
+                toolchain: toolchain.to_string(),
+                platform: CURRENT_PLATFORM.to_string(),
+                path,
+            });
+        }
+        Self::open(&path)
+    }
+
+    // Extracts an archive, checks that it works on this host and installs it
+    // in place of any bundle for the same toolchain.
+    pub fn import(archive_path: &Path) -> Result<Self> {
+        let bundles_directory = Self::bundles_directory();
+        fs::create_dir_all(&bundles_directory).with_context(|| {
+            format!("Failed to create directory {}", bundles_directory.display())
+        })?;
+
+        let staging = TempDir::new_in(&bundles_directory)?;
+        archive::unpack(archive_path, staging.path())?;
+        let bundle = Self::open(staging.path())?;
+        bundle.verify()?;
+
+        if bundle.manifest.pdf_generator {
+            let pdf_directory = bundles_directory.join(PDF_DIR);
+            fs::create_dir_all(&pdf_directory)?;
+            fs::copy(
+                bundle.root.join(PDF_DIR).join(pdf_generator_file()),
+                pdf_directory.join(pdf_generator_file()),
+            )
+            .with_context(|| "Failed to install the PDF generator")?;
+        }
+
+        let destination = Self::installed_path(&bundle.manifest.toolchain);
+        if destination.exists() {
+            fs::remove_dir_all(&destination).with_context(|| {
+                format!(
+                    "Failed to remove previous bundle at {}",
+                    destination.display()
+                )
+            })?;
+        }
+        fs::rename(staging.keep(), &destination)
+            .with_context(|| format!("Failed to install bundle at {}", destination.display()))?;
+
+        print_info(&format!(
+            "Imported detector bundle for {} to {}",
+            bundle.manifest.toolchain,
+            destination.display()
+        ));
+        Self::open(&destination)
+    }
+
+    pub fn manifest(&self) -> &BundleManifest {
+        &self.manifest
+    }
+
+    pub fn detector_names(&self, blockchain: BlockChain) -> Result<Vec<String>> {
+        Ok(self
+            .detectors(blockchain)?
+            .iter()
+            .map(|detector| detector.name.clone())
+            .collect())
+    }
+
+    pub fn detector_paths(
+        &self,
+        blockchain: BlockChain,
+        used_detectors: &[String],
+    ) -> Result<Vec<PathBuf>> {
+        let directory = self.detectors_directory(blockchain);
+        Ok(self
+            .detectors(blockchain)?
+            .iter()
+            .filter(|detector| used_detectors.contains(&detector.name))
+            .map(|detector| directory.join(&detector.file))
+            .collect())
+    }
+
+    // Metadata saved at export time, restricted to the used detectors.
+    pub fn detectors_info(
+        &self,
+        blockchain: BlockChain,
+        used_detectors: &[String],
+    ) -> Result<LintStore> {
+        let path = self.detectors_directory(blockchain).join(LINTS_FILE);
+        let lints = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let lints: LintStore = serde_json::from_str(&lints)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+
+        let mut store = LintStore::new();
+        for lint in lints.iter() {
+            if used_detectors
+                .iter()
+                .any(|name| name.replace('-', "_") == lint.id)
+            {
+                store.insert(lint.clone());
+            }
+        }
+        Ok(store)
+    }
+
+    pub fn scout_driver(&self) -> PathBuf {
+        self.root.join(BIN_DIR).join(scout_driver_file())
+    }
+
+    pub fn detector_helper(&self) -> PathBuf {
+        self.root.join(BIN_DIR).join(detector_helper_file())
+    }
+
+    // The PDF generator does not depend on the toolchain, so it is shared by
+    // every installed bundle of this Scout version.
+    pub fn installed_pdf_generator() -> Option<PathBuf> {
+        let path = Self::bundles_directory()
+            .join(PDF_DIR)
+            .join(pdf_generator_file());
+        path.exists().then_some(path)
+    }
+
+    fn bundles_directory() -> PathBuf {
+        get_config_directory().join("bundles").join(SCOUT_VERSION)
+    }
+
+    fn installed_path(toolchain: &str) -> PathBuf {
+        Self::bundles_directory().join(format!("{}-{}", toolchain, CURRENT_PLATFORM))
+    }
+
+    fn detectors(&self, blockchain: BlockChain) -> Result<&Vec<BundledDetector>> {
+        self.manifest
+            .detectors
+            .get(&blockchain.to_string())
+            .ok_or_else(|| BundleError::MissingBlockchain(blockchain).into())
+    }
+
+    fn detectors_directory(&self, blockchain: BlockChain) -> PathBuf {
+        self.root.join(DETECTORS_DIR).join(blockchain.to_string())
+    }
+
+    // Loads every detector with the bundled helper, which fails early when the
+    // toolchain is missing instead of in the middle of an analysis.
+    fn verify(&self) -> Result<()> {
+        for (blockchain, detectors) in &self.manifest.detectors {
+            let directory = self.root.join(DETECTORS_DIR).join(blockchain);
+            let paths = detectors
+                .iter()
+                .map(|detector| directory.join(&detector.file))
+                .collect::<Vec<_>>();
+            get_detectors_info(&self.manifest.toolchain, &self.detector_helper(), &paths)
+                .map_err(|_| BundleError::VerificationFailed(self.manifest.toolchain.clone()))?;
+        }
+        Ok(())
+    }
+}
+
+fn scout_driver_file() -> String {
+    format!("scout-driver{}", consts::EXE_SUFFIX)
+}
+
+fn detector_helper_file() -> String {
+    format!("detector-helper{}", consts::EXE_SUFFIX)
+}
+
+fn pdf_generator_file() -> String {
+    format!("{}html_to_pdf{}", consts::DLL_PREFIX, consts::DLL_SUFFIX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BundleManifest, BundledDetector, DETECTORS_DIR, DetectorBundle, LINTS_FILE, MANIFEST_FILE,
+        archive,
+    };
+    use crate::{
+        cli_args::BlockChain,
+        util::detectors_info::{LintInfo, LintStore},
+    };
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reads_detectors_back_from_an_archive() {
+        let source = TempDir::new().unwrap();
+        let mut manifest = BundleManifest::new("nightly-2025-08-07");
+        manifest.detectors.insert(
+            "soroban".to_string(),
+            ["unsafe-unwrap", "unsafe-expect"]
+                .iter()
+                .map(|name| BundledDetector {
+                    name: name.to_string(),
+                    file: format!("lib{}.so", name.replace('-', "_")),
+                })
+                .collect(),
+        );
+        let mut lints = LintStore::new();
+        for id in ["unsafe_unwrap", "unsafe_expect"] {
+            lints.insert(LintInfo {
+                id: id.to_string(),
+                ..LintInfo::default()
+            });
+        }
+        let detectors_directory = source.path().join(DETECTORS_DIR).join("soroban");
+        fs::create_dir_all(&detectors_directory).unwrap();
+        fs::write(
+            detectors_directory.join(LINTS_FILE),
+            serde_json::to_string(&lints).unwrap(),
+        )
+        .unwrap();
+        fs::write(
+            source.path().join(MANIFEST_FILE),
+            serde_json::to_string(&manifest).unwrap(),
+        )
+        .unwrap();
+
+        let archive_directory = TempDir::new().unwrap();
+        let archive_path = archive_directory.path().join("bundle.tar.gz");
+        archive::pack(source.path(), &archive_path).unwrap();
+        let destination = TempDir::new().unwrap();
+        archive::unpack(&archive_path, destination.path()).unwrap();
+
+        let bundle = DetectorBundle::open(destination.path()).unwrap();
+        let used = vec!["unsafe-unwrap".to_string()];
+        assert_eq!(bundle.manifest(), &manifest);
+        assert_eq!(
+            bundle.detector_paths(BlockChain::Soroban, &used).unwrap(),
+            [destination
+                .path()
+                .join(DETECTORS_DIR)
+                .join("soroban")
+                .join("libunsafe_unwrap.so")]
+        );
+        let info = bundle.detectors_info(BlockChain::Soroban, &used).unwrap();
+        assert!(info.find_by_id("unsafe_unwrap").is_some());
+        assert!(info.find_by_id("unsafe_expect").is_none());
+        assert!(bundle.detector_names(BlockChain::Ink).is_err());
+    }
+}
@@ -1,28 +1,54 @@
-use crate::util::print::print_info;
+use crate::{
+    scout::{
+        core::{project_info::PackageScope, triage::TriageStatus},
+        output::report::{Package, Severity},
+    },
+    util::print::print_info,
+};
 use anyhow::{Result, bail};
 use cargo_metadata::Metadata;
 use clap::{Parser, Subcommand, ValueEnum};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, path::PathBuf, process::Command};
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::PathBuf,
+    process::Command,
+};
 use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum BlockchainError {
-    #[error("No supported dependency found in Cargo.toml.\n     → Supported dependencies:\n{0}")]
-    UnsupportedDependency(String),
-
     #[error("Failed to determine project toolchain: {0}")]
     ToolchainError(String),
 }
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, EnumIter, Display, EnumString)]
+#[derive(
+    Debug,
+    Deserialize,
+    Serialize,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    EnumIter,
+    Display,
+    EnumString,
+    ValueEnum,
+)]
 #[strum(serialize_all = "kebab-case")]
 #[serde(rename_all = "kebab-case")]
 pub enum BlockChain {
     Ink,
     Soroban,
     SubstratePallets,
+    // Crates without a supported blockchain dependency, analyzed only with the
+    // `rust` detectors
+    #[value(alias = "generic")]
+    Rust,
 }
 
 impl BlockChain {
@@ -30,11 +56,13 @@ impl BlockChain {
         Self::iter().map(|e| e.to_string()).collect()
     }
 
-    pub fn get_detectors_path(&self) -> &str {
+    // Detectors run on top of the `rust` ones, if any.
+    pub fn get_detectors_path(&self) -> Option<&str> {
         match self {
-            BlockChain::Ink => "ink",
-            BlockChain::Soroban => "soroban",
-            BlockChain::SubstratePallets => "substrate-pallets",
+            BlockChain::Ink => Some("ink"),
+            BlockChain::Soroban => Some("soroban"),
+            BlockChain::SubstratePallets => Some("substrate-pallets"),
+            BlockChain::Rust => None,
         }
     }
 
@@ -96,6 +124,21 @@ impl BlockChain {
         Ok(default_toolchain.to_string())
     }
 
+    // Checked in this order, so a package depending on several of them gets
+    // the first one
+    const DEPENDENCIES: [(&'static str, BlockChain); 3] = [
+        ("soroban-sdk", BlockChain::Soroban),
+        ("ink", BlockChain::Ink),
+        ("frame-system", BlockChain::SubstratePallets),
+    ];
+
+    fn from_dependencies(has_dependency: impl Fn(&str) -> bool) -> Self {
+        Self::DEPENDENCIES
+            .into_iter()
+            .find(|(name, _)| has_dependency(name))
+            .map_or(BlockChain::Rust, |(_, blockchain)| blockchain)
+    }
+
     fn get_immediate_dependencies(metadata: &Metadata) -> HashSet<String> {
         let mut ret = HashSet::<String>::new();
         let root_packages = metadata
@@ -111,27 +154,90 @@ impl BlockChain {
     }
 
     #[tracing::instrument(name = "GET BLOCKCHAIN DEPENDENCY", level = "debug", skip_all)]
-    pub fn get_blockchain_dependency(metadata: &Metadata) -> Result<Self> {
+    pub fn get_blockchain_dependency(metadata: &Metadata) -> Self {
         let immediate_dependencies = Self::get_immediate_dependencies(metadata);
-        if immediate_dependencies.contains("soroban-sdk") {
-            Ok(BlockChain::Soroban)
-        } else if immediate_dependencies.contains("ink") {
-            Ok(BlockChain::Ink)
-        } else if immediate_dependencies.contains("frame-system") {
-            Ok(BlockChain::SubstratePallets)
-        } else {
-            let supported_dependencies = BlockChain::variants()
-                .into_iter()
-                .map(|chain| format!("        - {}", chain))
-                .collect::<Vec<_>>()
-                .join("\n");
-            bail!(BlockchainError::UnsupportedDependency(
-                supported_dependencies,
+        Self::from_dependencies(|name| immediate_dependencies.contains(name))
+    }
+
+    // Groups the analyzed packages by the blockchain of their own dependencies,
+    // or puts all of them in `forced` when it is given (`--blockchain`).
+    #[tracing::instrument(name = "GET PACKAGE BLOCKCHAINS", level = "debug", skip_all)]
+    pub fn get_package_blockchains(
+        metadata: &Metadata,
+        packages: &[Package],
+        forced: Option<Self>,
+    ) -> PackageBlockchains {
+        let mut ret = PackageBlockchains::default();
+        for package in packages {
+            let blockchain = forced.unwrap_or_else(|| {
+                let dependencies = metadata
+                    .packages
+                    .iter()
+                    .find(|p| p.id.to_string() == package.id)
+                    .map(|p| p.dependencies.as_slice())
+                    .unwrap_or_default();
+                Self::from_dependencies(|name| dependencies.iter().any(|dep| dep.name == name))
+            });
+            ret.insert(&package.name, blockchain);
+        }
+        ret
+    }
+}
+
+// Package names by blockchain, for workspaces that mix them.
+#[derive(Debug, Default, PartialEq)]
+pub struct PackageBlockchains {
+    pub chains: BTreeMap<BlockChain, Vec<String>>,
+}
+
+impl PackageBlockchains {
+    pub fn insert(&mut self, package: &str, blockchain: BlockChain) {
+        self.chains
+            .entry(blockchain)
+            .or_default()
+            .push(package.to_string());
+    }
+
+    // The blockchain a single-chain detection would have picked, used where
+    // the run needs just one (telemetry, toolchain).
+    pub fn primary(&self) -> BlockChain {
+        BlockChain::DEPENDENCIES
+            .into_iter()
+            .map(|(_, blockchain)| blockchain)
+            .find(|blockchain| self.chains.contains_key(blockchain))
+            .unwrap_or(BlockChain::Rust)
+    }
+
+    // Each blockchain gets its own run with its own `-p` list, so packages
+    // selected through the cargo arguments would be analyzed by every run.
+    pub fn validate_args(&self, args: &[String]) -> Result<()> {
+        if self.chains.len() > 1 && selects_packages(args) {
+            bail!(CliError::PackageSelectionWithMultipleChains(
+                self.chains
+                    .keys()
+                    .map(|chain| chain.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
             ));
         }
+        Ok(())
     }
 }
 
+// Cargo arguments that select packages on their own.
+const PACKAGE_SELECTION_ARGS: [&str; 5] = ["-p", "--package", "--workspace", "--all", "--exclude"];
+
+// Whether the cargo arguments given after `--` select packages.
+pub fn selects_packages(args: &[String]) -> bool {
+    args.iter().any(|arg| {
+        PACKAGE_SELECTION_ARGS
+            .iter()
+            .any(|x| arg == x || arg.starts_with(&format!("{x}=")))
+            // Cargo also takes the short flag attached, as in `-ptoken`
+            || arg.starts_with("-p")
+    })
+}
+
 #[derive(Debug, Parser)]
 #[clap(display_name = "cargo")]
 pub struct Cli {
@@ -141,7 +247,12 @@ pub struct Cli {
 
 #[cfg(test)]
 mod tests {
-    use super::BlockChain;
+    use super::{
+        BlockChain, BundleCommand, CargoSubCommand, Cli, OutputFormat, PackageBlockchains,
+        ScoutCommand,
+    };
+    use clap::{Parser, ValueEnum};
+    use std::collections::HashSet;
 
     #[test]
     fn parses_dated_nightly_with_target_triple() {
@@ -167,6 +278,117 @@ mod tests {
         let toolchain = BlockChain::parse_nightly_toolchain("1.89-x86_64-unknown-linux-gnu");
         assert!(toolchain.is_none());
     }
+
+    #[test]
+    fn output_dir_file_names_are_unique() {
+        let names = OutputFormat::value_variants()
+            .iter()
+            .map(OutputFormat::file_name)
+            .collect::<HashSet<_>>();
+        assert_eq!(names.len(), OutputFormat::value_variants().len());
+        assert_eq!(OutputFormat::MarkdownGithub.name(), "md-gh");
+    }
+
+    #[test]
+    fn parses_bundle_export_and_offline_runs() {
+        let CargoSubCommand::ScoutAudit(opts) = Cli::parse_from([
+            "cargo",
+            "scout-audit",
+            "bundle",
+            "export",
+            "scout.tar.gz",
+            "--blockchain",
+            "soroban,substrate-pallets",
+        ])
+        .subcmd;
+        match opts.command {
+            Some(ScoutCommand::Bundle(BundleCommand::Export {
+                path, blockchain, ..
+            })) => {
+                assert_eq!(path.to_str(), Some("scout.tar.gz"));
+                assert_eq!(blockchain.len(), 2);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        let CargoSubCommand::ScoutAudit(opts) =
+            Cli::parse_from(["cargo", "scout-audit", "--offline", "--", "--features", "x"]).subcmd;
+        assert!(opts.offline && opts.command.is_none());
+        assert_eq!(opts.args, ["--features", "x"]);
+    }
+
+    #[test]
+    fn groups_packages_by_blockchain() {
+        let CargoSubCommand::ScoutAudit(opts) =
+            Cli::parse_from(["cargo", "scout-audit", "--blockchain", "substrate-pallets"]).subcmd;
+        assert_eq!(opts.blockchain, Some(BlockChain::SubstratePallets));
+
+        let mut packages = PackageBlockchains::default();
+        packages.insert("utils", BlockChain::Rust);
+        packages.insert("pallet", BlockChain::SubstratePallets);
+        packages.insert("token", BlockChain::Soroban);
+        packages.insert("vault", BlockChain::Soroban);
+        assert_eq!(packages.chains[&BlockChain::Soroban], ["token", "vault"]);
+        assert_eq!(packages.primary(), BlockChain::Soroban);
+        assert_eq!(PackageBlockchains::default().primary(), BlockChain::Rust);
+    }
+
+    #[test]
+    fn rejects_package_selection_across_blockchains() {
+        let args = |args: &[&str]| args.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        let mut packages = PackageBlockchains::default();
+        packages.insert("token", BlockChain::Soroban);
+        packages.insert("vault", BlockChain::Soroban);
+        assert!(packages.validate_args(&args(&["-p", "token"])).is_ok());
+
+        packages.insert("pallet", BlockChain::SubstratePallets);
+        assert!(packages.validate_args(&args(&["--features", "x"])).is_ok());
+        for selection in [&["-p", "token"][..], &["-ptoken"], &["--workspace"]] {
+            assert!(packages.validate_args(&args(selection)).is_err());
+        }
+    }
+
+    #[test]
+    fn parses_package_and_target_selection() {
+        let CargoSubCommand::ScoutAudit(mut opts) = Cli::parse_from([
+            "cargo",
+            "scout-audit",
+            "-p",
+            "token,vault",
+            "--exclude-package",
+            "utils",
+            "--lib",
+            "--tests",
+            "--debug",
+        ])
+        .subcmd;
+        let scope = opts.package_scope();
+        assert_eq!(scope.include, ["token", "vault"]);
+        assert_eq!(scope.exclude, ["utils"]);
+
+        opts.prepare_args(BlockChain::Rust);
+        assert_eq!(opts.args, ["--lib", "--tests"]);
+
+        opts.args = vec!["--package=token".to_string()];
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn builds_generic_crates_for_the_host() {
+        let CargoSubCommand::ScoutAudit(mut opts) =
+            Cli::parse_from(["cargo", "scout-audit", "--blockchain", "generic"]).subcmd;
+        assert_eq!(opts.blockchain, Some(BlockChain::Rust));
+        assert_eq!(BlockChain::Rust.to_string(), "rust");
+
+        opts.prepare_args(BlockChain::Rust);
+        assert_eq!(opts.args, ["--release"]);
+        opts.args.clear();
+        opts.prepare_args(BlockChain::Soroban);
+        assert!(
+            opts.args
+                .contains(&"--target=wasm32-unknown-unknown".to_string())
+        );
+    }
 }
 
 #[derive(Error, Debug)]
@@ -174,6 +396,12 @@ pub enum CliError {
     #[error("The output path cannot be a directory (Path: '{0}')")]
     OutputPathIsDirectory(PathBuf),
 
+    #[error("The output directory cannot be a file (Path: '{0}')")]
+    OutputDirIsFile(PathBuf),
+
+    #[error("The template directory does not exist (Path: '{0}')")]
+    TemplateDirDoesNotExist(PathBuf),
+
     #[error("The scout sources path does not exist (Path: '{0}')")]
     ScoutSourcesPathDoesNotExist(PathBuf),
 
@@ -191,6 +419,34 @@ pub enum CliError {
 
     #[error("Manifest path must be a valid Cargo.toml file (Path: '{0}')")]
     ManifestPathIsNotFile(PathBuf),
+
+    #[error("Baseline file does not exist (Path: '{0}')")]
+    BaselinePathDoesNotExist(PathBuf),
+
+    #[error("The baseline path cannot be a directory (Path: '{0}')")]
+    BaselinePathIsDirectory(PathBuf),
+
+    #[error("Bundle archive does not exist (Path: '{0}')")]
+    BundleArchiveDoesNotExist(PathBuf),
+
+    #[error("The bundle path cannot be a directory (Path: '{0}')")]
+    BundlePathIsDirectory(PathBuf),
+
+    #[error("Report file does not exist (Path: '{0}')")]
+    ReportPathDoesNotExist(PathBuf),
+
+    #[error("--events cannot be combined with --message-format=json")]
+    EventsWithMessageFormat,
+
+    #[error(
+        "--package and --exclude-package cannot be combined with cargo package selection arguments"
+    )]
+    PackageSelectionInArgs,
+
+    #[error(
+        "Cargo package selection arguments cannot be used in a workspace with packages of more than one blockchain ({0}), use --package instead"
+    )]
+    PackageSelectionWithMultipleChains(String),
 }
 
 #[derive(Debug, Subcommand)]
@@ -198,6 +454,112 @@ pub enum CargoSubCommand {
     ScoutAudit(Scout),
 }
 
+#[derive(Clone, Debug, Subcommand, Serialize, Deserialize)]
+pub enum ScoutCommand {
+    /// Package the prebuilt detectors to run Scout without network access
+    #[clap(subcommand)]
+    Bundle(BundleCommand),
+    /// Show or change whether Scout sends telemetry and checks for updates
+    #[clap(subcommand)]
+    Telemetry(TelemetryCommand),
+    /// Work with the HTML and Markdown report templates
+    #[clap(subcommand)]
+    Template(TemplateCommand),
+    /// Record the status of a finding, which later runs show in the reports
+    #[clap(subcommand)]
+    Triage(TriageCommand),
+    /// Compare two JSON reports and list the new, fixed and unchanged findings
+    Diff {
+        #[clap(value_name = "OLD", help = "Report of the earlier run", value_hint = clap::ValueHint::FilePath)]
+        old: PathBuf,
+
+        #[clap(value_name = "NEW", help = "Report of the later run", value_hint = clap::ValueHint::FilePath)]
+        new: PathBuf,
+
+        #[clap(long, value_name = "PATH", help = "Also write the comparison as Markdown", value_hint = clap::ValueHint::FilePath)]
+        markdown: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand, Serialize, Deserialize)]
+pub enum TemplateCommand {
+    /// Write the built-in templates to a directory, to customize them with `--template`
+    Export {
+        #[clap(value_name = "DIR", help = "Directory to write the templates to", value_hint = clap::ValueHint::DirPath)]
+        path: PathBuf,
+    },
+}
+
+#[derive(Clone, Debug, Subcommand, Serialize, Deserialize)]
+pub enum TriageCommand {
+    /// Give a finding a status, replacing any previous one
+    Mark {
+        #[clap(
+            value_name = "FINGERPRINT",
+            help = "Fingerprint of the finding, or its first 12 or more characters"
+        )]
+        fingerprint: String,
+
+        #[clap(long, value_enum, help = "Status of the finding")]
+        status: TriageStatus,
+
+        #[clap(
+            long,
+            value_name = "NAME",
+            help = "Who triaged the finding, defaults to the git user name"
+        )]
+        author: Option<String>,
+
+        #[clap(long, value_name = "TEXT", help = "Why the finding has this status")]
+        comment: Option<String>,
+    },
+    /// Remove the status of a finding
+    Unmark {
+        #[clap(
+            value_name = "FINGERPRINT",
+            help = "Fingerprint of the finding, or its first 12 or more characters"
+        )]
+        fingerprint: String,
+    },
+    /// List the triaged findings
+    List,
+}
+
+#[derive(Clone, Debug, Subcommand, Serialize, Deserialize)]
+pub enum TelemetryCommand {
+    /// Show whether telemetry is enabled and the report that would be sent
+    Status,
+    /// Allow telemetry again after `disable`
+    Enable,
+    /// Stop sending telemetry and checking for updates, and forget the user ID
+    Disable,
+}
+
+#[derive(Clone, Debug, Subcommand, Serialize, Deserialize)]
+pub enum BundleCommand {
+    /// Build the detectors, drivers and detector metadata into an archive
+    Export {
+        #[clap(value_name = "PATH", help = "Path of the archive to create", value_hint = clap::ValueHint::FilePath)]
+        path: PathBuf,
+
+        #[clap(
+            long,
+            value_name = "BLOCKCHAINS",
+            help = "Blockchains to include, defaults to those of the project (comma-separated)",
+            value_delimiter = ','
+        )]
+        blockchain: Vec<BlockChain>,
+
+        #[clap(long, help = "Include the PDF generator")]
+        with_pdf: bool,
+    },
+    /// Install an archive created with `bundle export` for `--offline` runs
+    Import {
+        #[clap(value_name = "PATH", help = "Path of the archive to install", value_hint = clap::ValueHint::FilePath)]
+        path: PathBuf,
+    },
+}
+
 #[derive(Debug, Default, Clone, ValueEnum, PartialEq, Serialize, Deserialize)]
 pub enum OutputFormat {
     #[default]
@@ -212,11 +574,51 @@ pub enum OutputFormat {
     MarkdownGithub,
     Sarif,
     Pdf,
+    Junit,
+    GitlabCodeQuality,
+    SonarGeneric,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventFormat {
+    // One JSON object per line
+    Jsonl,
+}
+
+impl OutputFormat {
+    // File written for this format by `--output-dir`. Unlike the defaults used
+    // without it, every format gets a different name.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            OutputFormat::Html => "report.html",
+            OutputFormat::Json => "report.json",
+            OutputFormat::RawJson => "raw-report.jsonl",
+            OutputFormat::RawSingleJson => "raw-report.json",
+            OutputFormat::UnfilteredJson => "unfiltered-report.json",
+            OutputFormat::Markdown => "report.md",
+            OutputFormat::MarkdownGithub => "report-github.md",
+            OutputFormat::Sarif => "report.sarif",
+            OutputFormat::Pdf => "report.pdf",
+            OutputFormat::Junit => "report.xml",
+            OutputFormat::GitlabCodeQuality => "gl-code-quality-report.json",
+            OutputFormat::SonarGeneric => "sonar-issues.json",
+        }
+    }
+
+    // Name used on the command line (`md-gh`, `raw-json`...).
+    pub fn name(&self) -> String {
+        self.to_possible_value()
+            .map(|value| value.get_name().to_string())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone, Debug, Default, Parser, Serialize, Deserialize)]
 #[command(author, version, about, long_about = None)]
 pub struct Scout {
+    #[clap(subcommand)]
+    pub command: Option<ScoutCommand>,
+
     #[clap(short, long, value_name = "PATH", help = "Path to Cargo.toml", value_hint = clap::ValueHint::FilePath)]
     pub manifest_path: Option<PathBuf>,
 
@@ -259,9 +661,59 @@ pub struct Scout {
     #[clap(long, value_name = "PATH", help = "Path to save the output file", value_hint = clap::ValueHint::FilePath)]
     pub output_path: Option<PathBuf>,
 
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Write every output format to its own file in this directory",
+        value_hint = clap::ValueHint::DirPath,
+        conflicts_with = "output_path"
+    )]
+    pub output_dir: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "DIR",
+        help = "Directory with custom HTML and Markdown report templates",
+        value_hint = clap::ValueHint::DirPath
+    )]
+    pub template: Option<PathBuf>,
+
     #[clap(long, value_name = "PATH", help = "Path to custom detectors workspace", value_hint = clap::ValueHint::DirPath)]
     pub local_detectors: Option<PathBuf>,
 
+    #[clap(
+        long,
+        value_name = "BLOCKCHAIN",
+        help = "Analyze every package as this blockchain instead of detecting it from its dependencies"
+    )]
+    pub blockchain: Option<BlockChain>,
+
+    #[clap(
+        short,
+        long,
+        value_name = "PACKAGES",
+        help = "Only analyze these workspace members (comma-separated)",
+        value_delimiter = ','
+    )]
+    pub package: Vec<String>,
+
+    #[clap(
+        long,
+        value_name = "PACKAGES",
+        help = "Don't analyze these workspace members (comma-separated)",
+        value_delimiter = ','
+    )]
+    pub exclude_package: Vec<String>,
+
+    #[clap(long, help = "Only analyze the library targets")]
+    pub lib: bool,
+
+    #[clap(long, help = "Only analyze the binary targets")]
+    pub bins: bool,
+
+    #[clap(long, help = "Only analyze the test targets")]
+    pub tests: bool,
+
     #[clap(short, long, help = "Enable verbose output")]
     pub verbose: bool,
 
@@ -300,13 +752,104 @@ pub struct Scout {
         value_hint = clap::ValueHint::DirPath
     )]
     pub scout_source: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Only report findings that are not present in this baseline file",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    pub baseline: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Save the current findings as a baseline file",
+        value_hint = clap::ValueHint::FilePath
+    )]
+    pub write_baseline: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "GIT_REF",
+        help = "Only report findings on lines changed since this git reference"
+    )]
+    pub diff_base: Option<String>,
+
+    #[clap(
+        long,
+        help = "List the findings outside the diff in the Markdown and HTML reports",
+        requires = "diff_base"
+    )]
+    pub show_pre_existing: bool,
+
+    #[clap(
+        long,
+        value_name = "SEVERITY",
+        help = "Only fail when a finding has at least this severity"
+    )]
+    pub fail_on: Option<Severity>,
+
+    #[clap(
+        long,
+        value_name = "GLOBS",
+        help = "Only report findings in files matching these patterns (comma-separated)",
+        value_delimiter = ','
+    )]
+    pub include_paths: Vec<String>,
+
+    #[clap(
+        long,
+        value_name = "GLOBS",
+        help = "Ignore findings in files matching these patterns (comma-separated)",
+        value_delimiter = ','
+    )]
+    pub exclude_paths: Vec<String>,
+
+    #[clap(
+        long,
+        help = "Run without network access, using the detectors of an imported bundle",
+        conflicts_with_all = ["local_detectors", "scout_source"]
+    )]
+    pub offline: bool,
+
+    #[clap(
+        long,
+        help = "Don't send telemetry or check for updates (also SCOUT_NO_TELEMETRY=1)"
+    )]
+    pub no_telemetry: bool,
+
+    #[clap(
+        long,
+        help = "Analyze every package instead of reusing the findings of unchanged ones"
+    )]
+    pub no_cache: bool,
+
+    #[clap(
+        long,
+        help = "Browse the findings in an interactive terminal UI instead of printing them"
+    )]
+    pub tui: bool,
+
+    #[clap(
+        long,
+        value_name = "FORMAT",
+        help = "Write progress and results to stdout as machine-readable events",
+        conflicts_with = "tui"
+    )]
+    pub events: Option<EventFormat>,
+
+    // Set by the library API, also silences scout-driver
+    #[clap(skip)]
+    #[serde(default)]
+    pub quiet: bool,
 }
 
 impl Scout {
     pub fn prepare_args(&mut self, blockchain: BlockChain) {
-        // Only add default target args if not a substrate-pallet project
-        let is_substrate_pallet = matches!(blockchain, BlockChain::SubstratePallets);
-        if !is_substrate_pallet && !self.args.iter().any(|x| x.contains("--target=")) {
+        // Pallets and generic crates are built for the host
+        let native_target = matches!(blockchain, BlockChain::SubstratePallets | BlockChain::Rust);
+        if !native_target && !self.args.iter().any(|x| x.contains("--target=")) {
             self.args.extend([
                 "--target=wasm32-unknown-unknown".to_string(),
                 "--no-default-features".to_string(),
@@ -314,9 +857,30 @@ impl Scout {
             ]);
         }
 
+        for (selected, flag) in [
+            (self.lib, "--lib"),
+            (self.bins, "--bins"),
+            (self.tests, "--tests"),
+        ] {
+            if selected && !self.args.iter().any(|x| x == flag) {
+                self.args.push(flag.to_string());
+            }
+        }
+
         if !self.debug {
             self.args.push("--release".to_string());
         }
+
+        if self.offline && !self.args.iter().any(|x| x == "--offline") {
+            self.args.push("--offline".to_string());
+        }
+    }
+
+    pub fn package_scope(&self) -> PackageScope {
+        PackageScope {
+            include: self.package.clone(),
+            exclude: self.exclude_package.clone(),
+        }
     }
 
     pub fn validate(&self) -> Result<()> {
@@ -327,6 +891,18 @@ impl Scout {
             bail!(CliError::OutputPathIsDirectory(path.clone()));
         }
 
+        if let Some(path) = &self.output_dir
+            && path.is_file()
+        {
+            bail!(CliError::OutputDirIsFile(path.clone()));
+        }
+
+        if let Some(path) = &self.template
+            && !path.is_dir()
+        {
+            bail!(CliError::TemplateDirDoesNotExist(path.clone()));
+        }
+
         if let Some(path) = &self.scout_source {
             if !path.exists() {
                 bail!(CliError::ScoutSourcesPathDoesNotExist(path.clone()));
@@ -354,6 +930,54 @@ impl Scout {
             }
         }
 
+        if let Some(path) = &self.baseline {
+            if !path.exists() {
+                bail!(CliError::BaselinePathDoesNotExist(path.clone()));
+            }
+            if path.is_dir() {
+                bail!(CliError::BaselinePathIsDirectory(path.clone()));
+            }
+        }
+
+        if let Some(path) = &self.write_baseline
+            && path.is_dir()
+        {
+            bail!(CliError::BaselinePathIsDirectory(path.clone()));
+        }
+
+        if self.events.is_some() && self.args.iter().any(|arg| arg == "--message-format=json") {
+            bail!(CliError::EventsWithMessageFormat);
+        }
+
+        if !self.package_scope().is_empty() && selects_packages(&self.args) {
+            bail!(CliError::PackageSelectionInArgs);
+        }
+
+        match &self.command {
+            Some(ScoutCommand::Bundle(BundleCommand::Export { path, .. })) if path.is_dir() => {
+                bail!(CliError::BundlePathIsDirectory(path.clone()));
+            }
+            Some(ScoutCommand::Bundle(BundleCommand::Import { path })) => {
+                if !path.exists() {
+                    bail!(CliError::BundleArchiveDoesNotExist(path.clone()));
+                }
+                if path.is_dir() {
+                    bail!(CliError::BundlePathIsDirectory(path.clone()));
+                }
+            }
+            Some(ScoutCommand::Diff { old, new, markdown }) => {
+                if let Some(path) = [old, new].into_iter().find(|path| !path.is_file()) {
+                    bail!(CliError::ReportPathDoesNotExist(path.clone()));
+                }
+                if let Some(path) = markdown
+                    && path.is_dir()
+                {
+                    bail!(CliError::OutputPathIsDirectory(path.clone()));
+                }
+            }
+            _ => {}
+        }
+
         Ok(())
     }
 
@@ -1,24 +1,61 @@
 use crate::{
     cli_args::{BlockChain, OutputFormat},
-    util::print::print_info,
+    scout::{
+        core::{path_filter::PathFilterConfig, project_info::PackageScope},
+        output::report::Severity,
+    },
+    util::{detectors_info::LintStore, print::print_info},
 };
 use anyhow::{Context, Result, anyhow};
 use cargo_metadata::{Metadata, camino::Utf8PathBuf};
 use serde::{Deserialize, Serialize};
-use std::fs;
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
 
+// The project configuration file, read once per run.
 #[derive(Debug, Serialize, Deserialize, Default)]
-struct ScoutConfig {
+pub struct ScoutConfig {
     #[serde(default)]
     pub exclude: Vec<String>,
     #[serde(default)]
     pub output_format: Vec<OutputFormat>,
+    #[serde(default)]
+    pub fail_on: Option<Severity>,
+    #[serde(default)]
+    pub severity_overrides: HashMap<String, Severity>,
+    #[serde(flatten)]
+    pub paths: PathFilterConfig,
+    #[serde(default)]
+    pub require_justification: bool,
+    // Also covers the update check. Enabled unless set to `false`.
+    #[serde(default)]
+    pub telemetry: Option<bool>,
+    #[serde(default)]
+    pub report: ReportConfig,
+    #[serde(default)]
+    pub packages: PackageScope,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct ReportConfig {
+    // Relative to the workspace root.
+    #[serde(default)]
+    pub template: Option<PathBuf>,
 }
 
 pub struct ProfileConfig {
     pub blockchain: BlockChain,
     pub detector_names: Vec<String>,
     pub output_format: Vec<OutputFormat>,
+    pub fail_on: Option<Severity>,
+    // Keyed by detector id (`unsafe_unwrap`), as in the `LintStore`.
+    pub severity_overrides: HashMap<String, Severity>,
+    pub paths: PathFilterConfig,
+    pub require_justification: bool,
+    pub template: Option<PathBuf>,
 }
 
 impl ProfileConfig {
@@ -26,18 +63,48 @@ impl ProfileConfig {
         blockchain: BlockChain,
         detector_names: Vec<String>,
         output_format: Vec<OutputFormat>,
+        fail_on: Option<Severity>,
     ) -> Self {
         Self {
             blockchain,
             detector_names,
             output_format,
+            fail_on,
+            severity_overrides: HashMap::new(),
+            paths: PathFilterConfig::default(),
+            require_justification: false,
+            template: None,
         }
     }
 
-    pub fn get_config(&self, metadata: &Metadata) -> Result<ProfileConfig> {
-        match self.load_project_config(metadata)? {
+    pub fn get_config(
+        &self,
+        config: Option<ScoutConfig>,
+        metadata: &Metadata,
+    ) -> Result<ProfileConfig> {
+        if config.is_some() {
+            print_info("Using project configuration file, please check it carefully.");
+        }
+        self.apply_project_config(config, metadata.workspace_root.as_std_path())
+    }
+
+    fn apply_project_config(
+        &self,
+        config: Option<ScoutConfig>,
+        workspace_root: &Path,
+    ) -> Result<ProfileConfig> {
+        match config {
             Some(config) => {
-                print_info("Using project configuration file, please check it carefully.");
+                self.validate_config(&config)?;
+                let template = config.report.template.map(|path| workspace_root.join(path));
+                if let Some(template) = &template
+                    && !template.is_dir()
+                {
+                    return Err(anyhow!(
+                        "Configuration error: Template directory '{}' does not exist",
+                        template.display()
+                    ));
+                }
                 Ok(ProfileConfig {
                     blockchain: self.blockchain,
                     detector_names: self
@@ -47,18 +114,59 @@ impl ProfileConfig {
                         .cloned()
                         .collect(),
                     output_format: config.output_format,
+                    // The command line takes precedence over the project configuration
+                    fail_on: self.fail_on.or(config.fail_on),
+                    severity_overrides: config
+                        .severity_overrides
+                        .into_iter()
+                        .map(|(name, severity)| (name.replace('-', "_"), severity))
+                        .collect(),
+                    paths: config.paths,
+                    require_justification: config.require_justification,
+                    template,
                 })
             }
             None => Ok(ProfileConfig {
                 blockchain: self.blockchain,
                 detector_names: self.detector_names.clone(),
                 output_format: self.output_format.clone(),
+                fail_on: self.fail_on,
+                severity_overrides: HashMap::new(),
+                paths: PathFilterConfig::default(),
+                require_justification: false,
+                template: None,
             }),
         }
     }
 
-    fn load_project_config(&self, metadata: &Metadata) -> Result<Option<ScoutConfig>> {
-        let Some(config_path) = self.find_config_path(metadata) else {
+    // Reads only the `telemetry` key, which is needed before the detectors are
+    // known and the rest of the configuration can be validated.
+    pub fn telemetry_enabled(config: Option<&ScoutConfig>) -> bool {
+        config.and_then(|config| config.telemetry).unwrap_or(true)
+    }
+
+    // Like `telemetry_enabled`, needed to know the packages before anything
+    // else. The command line replaces each list when given.
+    pub fn package_scope(config: Option<&ScoutConfig>, cli: &PackageScope) -> PackageScope {
+        let config = config
+            .map(|config| config.packages.clone())
+            .unwrap_or_default();
+        PackageScope {
+            include: if cli.include.is_empty() {
+                config.include
+            } else {
+                cli.include.clone()
+            },
+            exclude: if cli.exclude.is_empty() {
+                config.exclude
+            } else {
+                cli.exclude.clone()
+            },
+        }
+    }
+
+    pub fn read_project_config(metadata: &Metadata) -> Result<Option<ScoutConfig>> {
+        let Some(config_path) = Self::find_config_path(metadata) else {
             return Ok(None);
         };
 
@@ -68,13 +176,10 @@ impl ProfileConfig {
         let config: ScoutConfig = serde_yaml::from_str(&contents)
             .with_context(|| format!("Failed to parse config file: {:?}", config_path))?;
 
-        // Validate config structure
-        self.validate_config(&config)?;
-
         Ok(Some(config))
     }
 
-    fn find_config_path(&self, metadata: &Metadata) -> Option<Utf8PathBuf> {
+    fn find_config_path(metadata: &Metadata) -> Option<Utf8PathBuf> {
         let config_locations = [
             // First check package-level config
             metadata
@@ -106,6 +211,109 @@ impl ProfileConfig {
             }
         }
 
+        // Validate that all overridden detectors exist in the available detectors
+        for name in config.severity_overrides.keys() {
+            if !self.detector_names.contains(&name.replace('_', "-")) {
+                return Err(anyhow!(
+                    "Configuration error: Unknown detector '{}' in severity_overrides",
+                    name
+                ));
+            }
+        }
+
+        // Validate that all detectors with path exclusions exist in the available detectors
+        for name in config.paths.detector_exclude_paths.keys() {
+            if !self.detector_names.contains(&name.replace('_', "-")) {
+                return Err(anyhow!(
+                    "Configuration error: Unknown detector '{}' in detector_exclude_paths",
+                    name
+                ));
+            }
+        }
+
         Ok(())
     }
 }
+
+// Applies the project's severity policy to the detectors metadata. Must run
+// before anything reads the severities, e.g. the `[SEVERITY]` message tags.
+pub fn apply_severity_overrides(
+    detectors_info: &mut LintStore,
+    severity_overrides: &HashMap<String, Severity>,
+) {
+    for (id, severity) in severity_overrides.iter() {
+        if let Some(lint) = detectors_info.find_by_id_mut(id) {
+            lint.severity = severity.as_lint_severity().to_string();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProfileConfig, ScoutConfig, apply_severity_overrides};
+    use crate::{
+        cli_args::BlockChain,
+        scout::output::report::Severity,
+        util::detectors_info::{LintInfo, LintStore},
+    };
+    use std::path::Path;
+
+    fn profile() -> ProfileConfig {
+        ProfileConfig::new(
+            BlockChain::Soroban,
+            vec!["unsafe-unwrap".to_string(), "overflow-check".to_string()],
+            Vec::new(),
+            None,
+        )
+    }
+
+    fn apply(yaml: &str) -> anyhow::Result<ProfileConfig> {
+        let config = serde_yaml::from_str::<ScoutConfig>(yaml).unwrap();
+        profile().apply_project_config(Some(config), Path::new("/workspace"))
+    }
+
+    #[test]
+    fn severity_override_keys_use_detector_ids() {
+        let config =
+            apply("severity_overrides:\n  unsafe-unwrap: critical\n  overflow_check: minor\n")
+                .unwrap();
+        assert_eq!(
+            config.severity_overrides.get("unsafe_unwrap"),
+            Some(&Severity::Critical)
+        );
+        assert_eq!(
+            config.severity_overrides.get("overflow_check"),
+            Some(&Severity::Minor)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_detectors_in_severity_overrides() {
+        let error = apply("severity_overrides:\n  unsafe-expect: critical\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Configuration error: Unknown detector 'unsafe-expect' in severity_overrides"
+        );
+    }
+
+    #[test]
+    fn overrides_rewrite_detector_severities() {
+        let config = apply("severity_overrides:\n  unsafe-unwrap: critical\n").unwrap();
+        let mut detectors_info = LintStore::new();
+        for (id, severity) in [("unsafe_unwrap", "Minor"), ("overflow_check", "Medium")] {
+            detectors_info.insert(LintInfo {
+                id: id.to_string(),
+                severity: severity.to_string(),
+                ..Default::default()
+            });
+        }
+
+        apply_severity_overrides(&mut detectors_info, &config.severity_overrides);
+
+        let severity = |id: &str| detectors_info.find_by_id(id).unwrap().severity.clone();
+        assert_eq!(severity("unsafe_unwrap"), "Critical");
+        assert_eq!(severity("overflow_check"), "Medium");
+    }
+}
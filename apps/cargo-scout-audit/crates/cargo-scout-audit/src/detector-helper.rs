@@ -4,17 +4,9 @@ use crate::{
     util::{build_and_run::PackageToBuild, detectors_info::LintStore},
 };
 use anyhow::{Result, anyhow};
-use std::path::PathBuf;
-
-pub fn get_detectors_info(
-    toolchain: &str,
-    detectors_paths: &[PathBuf],
-    scout_sources: Option<&PathBuf>,
-) -> Result<LintStore> {
-    let input = HelperInput {
-        detectors_paths: crate::util::paths_to_strings(detectors_paths),
-    };
+use std::path::{Path, PathBuf};
 
+pub fn build_detector_helper(toolchain: &str, scout_sources: Option<&PathBuf>) -> Result<PathBuf> {
     let mut pkg = match scout_sources {
         Some(root) => PackageToBuild::new_local(root.clone()),
         None => PackageToBuild::new_remote(SCOUT_REPO, SCOUT_BRANCH, "scout-driver"),
@@ -23,10 +15,23 @@ pub fn get_detectors_info(
     pkg.build_error_message = "Failed to build detector-helper".to_string();
     pkg.toolchain = Some(format!("+{}", toolchain.trim_start_matches('+')));
     pkg.internal_path = Some("apps/cargo-scout-audit/crates/scout-driver".into());
-    let path = pkg.build_executable(Some("scout-driver"), "detector-helper")?;
+    pkg.build_executable(Some("scout-driver"), "detector-helper")
+}
+
+pub fn get_detectors_info(
+    toolchain: &str,
+    helper_path: &Path,
+    detectors_paths: &[PathBuf],
+) -> Result<LintStore> {
+    let input = HelperInput {
+        detectors_paths: crate::util::paths_to_strings(detectors_paths),
+    };
 
-    let output =
-        crate::interop::subprocess::run_subprocess::<_, HelperOutput>(toolchain, &path, &input)?;
+    let output = crate::interop::subprocess::run_subprocess::<_, HelperOutput>(
+        toolchain,
+        helper_path,
+        &input,
+    )?;
 
     match output.result {
         Ok(x) => Ok(x),
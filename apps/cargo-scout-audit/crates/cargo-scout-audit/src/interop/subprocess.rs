@@ -6,12 +6,12 @@ use std::{
     collections::HashMap,
     fs::File,
     io::{Read, Write},
-    path::PathBuf,
+    path::Path,
 };
 
 pub fn run_subprocess<I: Serialize, O: DeserializeOwned>(
     toolchain: &str,
-    exec: &PathBuf,
+    exec: &Path,
     input: &I,
 ) -> Result<O> {
     let (input_path, output_path) = get_paths(&input)?;
@@ -55,7 +55,7 @@ fn get_paths<I: Serialize>(input: &I) -> Result<(String, String)> {
 }
 
 fn do_it(
-    exec: &PathBuf,
+    exec: &Path,
     environment: HashMap<String, String>,
     input_path: &String,
     output_path: &String,
@@ -1,3 +1,5 @@
+pub mod api;
+pub mod bundle;
 pub mod cli_args;
 pub mod config;
 pub mod interop;
@@ -12,3 +14,5 @@ pub mod result;
 pub mod run;
 #[path = "scout-driver.rs"]
 pub mod scout_driver;
+
+pub use api::{AnalysisResult, ScoutBuilder};
@@ -1,5 +1,6 @@
 use cargo_scout_audit::{
     cli_args,
+    result::EXIT_TOOL_ERROR,
     run::run_scout,
     util::{
         logger::{get_subscriber, init_subscriber},
@@ -21,10 +22,10 @@ fn main() {
 
     match cli.subcmd {
         cli_args::CargoSubCommand::ScoutAudit(opts) => match run_scout(opts) {
-            Ok(_) => std::process::exit(0),
+            Ok(result) => std::process::exit(result.exit_code()),
             Err(e) => {
                 print_full_error(&e);
-                std::process::exit(1);
+                std::process::exit(EXIT_TOOL_ERROR);
             }
         },
     }
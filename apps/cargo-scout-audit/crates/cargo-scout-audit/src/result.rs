@@ -1,10 +1,19 @@
 use crate::scout::finding::Finding;
 use thiserror::Error;
 
+// Process exit codes, so CI can tell failing findings apart from tool errors.
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_TOOL_ERROR: i32 = 1;
+pub const EXIT_FINDINGS_ABOVE_THRESHOLD: i32 = 2;
+
 #[derive(Default, Debug)]
 pub struct ScoutResult {
     pub findings: Vec<Finding>,
     pub stdout_helper: String,
+    // Only set when a `--fail-on` threshold was requested.
+    pub threshold_exceeded: bool,
+    // Set when an analyzed crate failed to build, so the findings are incomplete.
+    pub build_failed: bool,
 }
 
 impl ScoutResult {
@@ -12,17 +21,32 @@ impl ScoutResult {
         Self {
             findings,
             stdout_helper,
+            threshold_exceeded: false,
+            build_failed: false,
         }
     }
     pub fn from_stdout(stdout_helper: String) -> Self {
         Self {
             findings: Vec::new(),
             stdout_helper,
+            threshold_exceeded: false,
+            build_failed: false,
         }
     }
     pub fn from_string<T: std::fmt::Display>(s: T) -> Self {
         Self::from_stdout(format!("{}\n", s))
     }
+    // An incomplete analysis takes precedence over the findings threshold, as
+    // the missing findings could change the outcome.
+    pub fn exit_code(&self) -> i32 {
+        if self.build_failed {
+            EXIT_TOOL_ERROR
+        } else if self.threshold_exceeded {
+            EXIT_FINDINGS_ABOVE_THRESHOLD
+        } else {
+            EXIT_SUCCESS
+        }
+    }
 }
 
 #[derive(Error, Debug)]
@@ -33,9 +57,6 @@ pub enum ScoutError {
     #[error("Failed to get project metadata:\n     → {0}")]
     MetadataFailed(#[source] anyhow::Error),
 
-    #[error("Failed to get blockchain dependency:\n     → {0}")]
-    BlockchainFailed(#[source] anyhow::Error),
-
     #[error("Failed to create default cargo configuration")]
     CargoConfigFailed,
 
@@ -53,4 +74,46 @@ pub enum ScoutError {
 
     #[error("Failed to run dylint:\n     → {0}")]
     RunDylintFailed(#[source] anyhow::Error),
+
+    #[error("Failed to parse the analysis output:\n     → {0}")]
+    ParseOutputFailed(#[source] anyhow::Error),
+
+    #[error("Failed to process baseline:\n     → {0}")]
+    BaselineFailed(#[source] anyhow::Error),
+
+    #[error("Failed to compute the changes since the diff base:\n     → {0}")]
+    DiffBaseFailed(#[source] anyhow::Error),
+
+    #[error("Failed to process detector bundle:\n     → {0}")]
+    BundleFailed(#[source] anyhow::Error),
+
+    #[error("Failed to export the report templates:\n     → {0}")]
+    TemplateExportFailed(#[source] anyhow::Error),
+
+    #[error("Failed to compare the reports:\n     → {0}")]
+    ReportDiffFailed(#[source] anyhow::Error),
+
+    #[error("Failed to process triaged findings:\n     → {0}")]
+    TriageFailed(#[source] anyhow::Error),
+
+    #[error("Failed to run the terminal UI:\n     → {0}")]
+    TuiFailed(#[source] anyhow::Error),
+
+    #[error("Failed to update telemetry settings:\n     → {0}")]
+    TelemetryFailed(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EXIT_FINDINGS_ABOVE_THRESHOLD, EXIT_SUCCESS, EXIT_TOOL_ERROR, ScoutResult};
+
+    #[test]
+    fn build_failures_take_precedence_over_the_threshold() {
+        let mut result = ScoutResult::default();
+        assert_eq!(result.exit_code(), EXIT_SUCCESS);
+        result.threshold_exceeded = true;
+        assert_eq!(result.exit_code(), EXIT_FINDINGS_ABOVE_THRESHOLD);
+        result.build_failed = true;
+        assert_eq!(result.exit_code(), EXIT_TOOL_ERROR);
+    }
 }
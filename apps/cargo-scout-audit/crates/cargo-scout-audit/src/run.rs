@@ -1,33 +1,60 @@
 use crate::{
-    cli_args::{BlockChain, OutputFormat, Scout},
-    config::ProfileConfig,
-    detector_helper::get_detectors_info as get_detectors_info_helped,
+    api::AnalysisResult,
+    bundle::{BundleError, DetectorBundle, export_bundle},
+    cli_args::{
+        BlockChain, BundleCommand, OutputFormat, Scout, ScoutCommand, TelemetryCommand,
+        TemplateCommand, TriageCommand,
+    },
+    config::{ProfileConfig, apply_severity_overrides},
     digest,
     result::{ScoutError, ScoutResult},
     scout::{
         core::{
-            findings::{get_crates, output_to_json, split_findings, temp_file_to_string},
+            baseline::{Baseline, BaselineComparison},
+            cache::{CacheLookup, FindingsCache},
+            diff_filter::{DiffFilter, PreExistingFinding},
+            findings::{
+                filter_output, get_crates, normalize_crate_name, output_to_findings,
+                split_findings, temp_file_to_string,
+            },
+            path_filter::PathFilter,
             project_info::Project,
-            telemetry::TelemetryClient,
+            suppression::{SuppressedFinding, Suppressions, UnjustifiedAllow, count_by_detector},
+            telemetry::{ClientType, TelemetryClient, TelemetryStatus},
+            triage::{TriageEntry, TriageStore, count_by_status, default_author},
             version_checker::VersionChecker,
         },
-        detectors::{builder::DetectorBuilder, configuration::DetectorsConfiguration},
+        detectors::{
+            builder::DetectorBuilder, configuration::DetectorsConfiguration, source::DetectorSource,
+        },
         finding::Finding,
-        output::report::Report,
+        output::{
+            events::{Event, EventStream, FindingEvent, SummaryEvent, detector_name},
+            report::{OmittedFindings, Package, Report, Severity},
+            report_diff::diff_reports,
+            table::{Importance, Row, SemanticColor, Table},
+            template::export_templates,
+            tui::browse_findings,
+        },
     },
     scout_driver::run_dylint,
     util::{
         detectors::{get_excluded_detectors, get_filtered_detectors, list_detectors},
         detectors_info::LintStore,
         logger::TracedError,
-        print::print_error,
+        print::{print_error, print_info, print_line, print_warning, quiet_scope},
     },
 };
-use anyhow::{Context, Ok, Result, anyhow};
+use anyhow::{Context, Ok, Result, anyhow, bail};
 use cargo::{GlobalContext, core::Verbosity};
 use cargo_metadata::Metadata;
+use itertools::Itertools;
 use serde_json::to_string_pretty;
-use std::{collections::HashSet, io::Write, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::Write,
+    path::PathBuf,
+};
 use terminal_color_builder::OutputFormatter;
 
 #[allow(clippy::large_enum_variant)]
@@ -36,9 +63,14 @@ enum EitherInfoOrScoutResult {
     ScoutResult(ScoutResult),
 }
 
-fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
+fn prepare_scout_input(opts: &mut Scout, events: &EventStream) -> Result<EitherInfoOrScoutResult> {
     opts.validate().map_err(ScoutError::ValidateFailed)?;
 
+    if let Some(command) = &opts.command {
+        run_command(opts, command)?;
+        return Ok(EitherInfoOrScoutResult::ScoutResult(ScoutResult::default()));
+    }
+
     if let Some(path) = opts.get_fail_path() {
         let _ = std::fs::File::create(path);
     }
@@ -52,11 +84,25 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
 
     let metadata = Project::get_metadata(&opts.manifest_path)
         .with_context(|| "Failed to get project metadata")?;
-    let blockchain =
-        BlockChain::get_blockchain_dependency(&metadata).map_err(ScoutError::BlockchainFailed)?;
-
-    // Prepare the args after we know the Blockchain type
-    opts.prepare_args(blockchain);
+    let project_config = ProfileConfig::read_project_config(&metadata)?;
+    let package_scope =
+        ProfileConfig::package_scope(project_config.as_ref(), &opts.package_scope());
+    let project_info =
+        Project::get_info(&metadata, &package_scope).map_err(ScoutError::GetProjectInfoFailed)?;
+    let package_blockchains =
+        BlockChain::get_package_blockchains(&metadata, &project_info.packages, opts.blockchain);
+    package_blockchains
+        .validate_args(&opts.args)
+        .map_err(ScoutError::ValidateFailed)?;
+    let blockchain = package_blockchains.primary();
+    if opts.blockchain.is_none()
+        && let Some(packages) = package_blockchains.chains.get(&BlockChain::Rust)
+    {
+        print_info(&format!(
+            "No supported blockchain dependency in {}, using the generic Rust detectors.",
+            packages.join(", ")
+        ));
+    }
 
     let toolchain = blockchain.get_toolchain(&metadata)?;
 
@@ -67,17 +113,24 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
         ));
     }
 
-    // Send telemetry data
-    let client_type = TelemetryClient::detect_client_type(opts);
-    let telemetry_client = TelemetryClient::new(blockchain, client_type);
-    let _ = telemetry_client.send_report();
+    let telemetry_status = TelemetryStatus::get(
+        opts.no_telemetry,
+        ProfileConfig::telemetry_enabled(project_config.as_ref()),
+    );
 
-    if let Err(e) = VersionChecker::new().check_for_updates() {
-        // This is not a critical error, so we don't need to bail and we don't need a ScoutError
-        print_error(&format!(
-            "Failed to check for scout updates.\n     → Caused by: {}",
-            e
-        ));
+    if !opts.offline && telemetry_status.is_enabled() {
+        // Send telemetry data
+        let client_type = TelemetryClient::detect_client_type(opts);
+        let telemetry_client = TelemetryClient::new(blockchain, client_type);
+        let _ = telemetry_client.send_report();
+
+        if let Err(e) = VersionChecker::new().check_for_updates() {
+            // This is not a critical error, so we don't need to bail and we don't need a ScoutError
+            print_error(&format!(
+                "Failed to check for scout updates.\n     → Caused by: {}",
+                e
+            ));
+        }
     }
 
     let cargo_config = GlobalContext::default().map_err(ScoutError::CargoConfigFailed.traced())?;
@@ -87,30 +140,94 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
         Verbosity::Quiet
     });
 
-    let detectors_config =
-        DetectorsConfiguration::get(blockchain, &toolchain, &opts.local_detectors, &metadata)
-            .map_err(ScoutError::DetectorsConfigFailed)?;
-
-    // Instantiate detectors
-    let detector_builder = DetectorBuilder::new(
-        &cargo_config,
-        &detectors_config,
-        &metadata,
-        opts.verbose,
-        &toolchain,
-    );
+    // Instantiate detectors, one source per blockchain of the workspace
+    let blockchains = package_blockchains
+        .chains
+        .keys()
+        .copied()
+        .collect::<Vec<_>>();
+    let bundle = if opts.offline {
+        Some(DetectorBundle::open_installed(&toolchain).map_err(ScoutError::BundleFailed)?)
+    } else {
+        None
+    };
+    let detectors_configs = if opts.offline {
+        Vec::new()
+    } else {
+        blockchains
+            .iter()
+            .map(|blockchain| {
+                DetectorsConfiguration::get(
+                    *blockchain,
+                    &toolchain,
+                    &opts.local_detectors,
+                    &metadata,
+                )
+            })
+            .collect::<Result<Vec<_>>>()
+            .map_err(ScoutError::DetectorsConfigFailed)?
+    };
+    let detector_sources = blockchains
+        .iter()
+        .enumerate()
+        .map(|(i, blockchain)| match &bundle {
+            Some(bundle) => DetectorSource::Bundle {
+                bundle,
+                blockchain: *blockchain,
+            },
+            None => DetectorSource::Build {
+                builder: DetectorBuilder::new(
+                    &cargo_config,
+                    &detectors_configs[i],
+                    &metadata,
+                    opts.verbose,
+                    &toolchain,
+                ),
+                scout_source: opts.scout_source.clone(),
+            },
+        })
+        .collect::<Vec<_>>();
 
-    let detectors_names = detector_builder
-        .get_detector_names()
-        .with_context(|| "Failed to get detector names")?;
+    let mut chain_detectors = Vec::new();
+    for source in detector_sources.iter() {
+        chain_detectors.push(
+            source
+                .get_detector_names()
+                .with_context(|| "Failed to get detector names")?,
+        );
+    }
+    // The `rust` detectors are shared by every blockchain
+    let detectors_names = chain_detectors.iter().flatten().unique().cloned().collect();
 
-    let profile_config =
-        ProfileConfig::new(blockchain, detectors_names, opts.output_format.clone())
-            .get_config(&metadata)?;
+    let profile_config = ProfileConfig::new(
+        blockchain,
+        detectors_names,
+        opts.output_format.clone(),
+        opts.fail_on,
+    )
+    .get_config(project_config, &metadata)?;
 
     let profile_detectors = profile_config.detector_names;
     let output_format = profile_config.output_format;
 
+    if opts.offline
+        && !cfg!(feature = "native_pdf")
+        && output_format.contains(&OutputFormat::Pdf)
+        && DetectorBundle::installed_pdf_generator().is_none()
+    {
+        return Err(ScoutError::BundleFailed(BundleError::MissingPdfGenerator.into()).into());
+    }
+    let fail_on = profile_config.fail_on;
+    let severity_overrides = profile_config.severity_overrides;
+
+    let mut path_config = profile_config.paths;
+    path_config.extend(&opts.include_paths, &opts.exclude_paths);
+    let path_filter =
+        PathFilter::new(&path_config).with_context(|| "Failed to parse path filters")?;
+    let require_justification = profile_config.require_justification;
+    // The command line takes precedence over the project configuration
+    let template_dir = opts.template.clone().or(profile_config.template);
+
     if opts.list_detectors {
         list_detectors(&profile_detectors);
         return Ok(EitherInfoOrScoutResult::ScoutResult(ScoutResult::default()));
@@ -124,12 +241,58 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
         profile_detectors
     };
 
-    let detectors_paths = detector_builder
-        .build(&filtered_detectors)
-        .map_err(ScoutError::BuildDetectorsFailed)?;
+    events.emit(Event::DetectorsBuilding {
+        detectors: filtered_detectors.len(),
+        offline: opts.offline,
+    });
+    let mut chain_runs = Vec::new();
+    let mut detectors_info = LintStore::new();
+    // The `rust` detectors are built for every blockchain, but reported once
+    let mut built_detectors = HashSet::new();
+    for ((blockchain, source), names) in blockchains
+        .iter()
+        .zip(detector_sources.iter())
+        .zip(chain_detectors.iter())
+    {
+        let used_detectors = filtered_detectors
+            .iter()
+            .filter(|name| names.contains(name))
+            .cloned()
+            .collect::<Vec<_>>();
+        let detectors_paths = source
+            .build(&used_detectors, &mut |path| {
+                if built_detectors.insert(path.to_path_buf()) {
+                    events.emit(Event::DetectorBuilt {
+                        name: detector_name(path),
+                        path: path.to_path_buf(),
+                    });
+                }
+            })
+            .map_err(ScoutError::BuildDetectorsFailed)?;
+        for lint in source
+            .get_detectors_info(&toolchain, &detectors_paths, &used_detectors)?
+            .iter()
+        {
+            detectors_info.insert(lint.clone());
+        }
+
+        let mut chain_opts = opts.clone();
+        chain_opts.prepare_args(*blockchain);
+        chain_runs.push(ChainRun {
+            packages: package_blockchains.chains[blockchain].clone(),
+            select_packages: blockchains.len() > 1 || !package_scope.is_empty(),
+            args: chain_opts.args,
+            detectors_paths,
+        });
+    }
+    let detectors_paths = chain_runs
+        .iter()
+        .flat_map(|run| run.detectors_paths.iter())
+        .unique()
+        .cloned()
+        .collect::<Vec<_>>();
 
-    let detectors_info =
-        get_detectors_info_helped(&toolchain, &detectors_paths, opts.scout_source.as_ref())?;
+    apply_severity_overrides(&mut detectors_info, &severity_overrides);
 
     if opts.detectors_metadata {
         let metadata = to_string_pretty(&detectors_info).unwrap();
@@ -139,10 +302,10 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
         ));
     }
 
-    let project_info = Project::get_info(&metadata).map_err(ScoutError::GetProjectInfoFailed)?;
-
     let inside_vscode = opts.args.contains(&"--message-format=json".to_string());
 
+    let driver_path = detector_sources[0].get_scout_driver(&toolchain)?;
+
     Ok(EitherInfoOrScoutResult::Info(RunInfo {
         inside_vscode,
         project_info,
@@ -150,11 +313,161 @@ fn prepare_scout_input(opts: &mut Scout) -> Result<EitherInfoOrScoutResult> {
         filtered_detectors,
         detectors_info,
         detectors_paths,
+        chain_runs,
+        driver_path,
         output_format,
         toolchain,
+        fail_on,
+        path_filter,
+        require_justification,
+        template_dir,
     }))
 }
 
+fn run_command(opts: &Scout, command: &ScoutCommand) -> Result<()> {
+    match command {
+        ScoutCommand::Bundle(BundleCommand::Export {
+            path,
+            blockchain,
+            with_pdf,
+        }) => export_bundle(opts, path, blockchain, *with_pdf).map_err(ScoutError::BundleFailed),
+        ScoutCommand::Bundle(BundleCommand::Import { path }) => DetectorBundle::import(path)
+            .map(|_| ())
+            .map_err(ScoutError::BundleFailed),
+        ScoutCommand::Telemetry(command) => {
+            run_telemetry_command(opts, command).map_err(ScoutError::TelemetryFailed)
+        }
+        ScoutCommand::Template(TemplateCommand::Export { path }) => {
+            export_templates(path).map_err(ScoutError::TemplateExportFailed)
+        }
+        ScoutCommand::Triage(command) => {
+            run_triage_command(opts, command).map_err(ScoutError::TriageFailed)
+        }
+        ScoutCommand::Diff { old, new, markdown } => {
+            diff_reports(old, new, markdown.as_deref()).map_err(ScoutError::ReportDiffFailed)
+        }
+    }?;
+    Ok(())
+}
+
+fn run_triage_command(opts: &Scout, command: &TriageCommand) -> Result<()> {
+    let metadata = Project::get_metadata(&opts.manifest_path)?;
+    let workspace_root = metadata.workspace_root.as_std_path();
+    let mut store = TriageStore::load(workspace_root)?;
+    match command {
+        TriageCommand::Mark {
+            fingerprint,
+            status,
+            author,
+            comment,
+        } => {
+            store.mark(TriageEntry {
+                fingerprint: fingerprint.to_lowercase(),
+                status: *status,
+                author: author.clone().unwrap_or_else(default_author),
+                comment: comment.clone().unwrap_or_default(),
+                date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            })?;
+            let path = store.save(workspace_root)?;
+            print_info(&format!(
+                "Marked {} as {} in {}.",
+                fingerprint,
+                status,
+                path.display()
+            ));
+        }
+        TriageCommand::Unmark { fingerprint } => {
+            if !store.unmark(&fingerprint.to_lowercase()) {
+                bail!("No triaged finding has the fingerprint {}", fingerprint);
+            }
+            let path = store.save(workspace_root)?;
+            print_info(&format!("Removed {} from {}.", fingerprint, path.display()));
+        }
+        TriageCommand::List => {
+            if store.findings.is_empty() {
+                println!("No findings have been triaged.");
+                return Ok(());
+            }
+            let mut header =
+                Row::from_strs(&["Fingerprint", "Status", "Author", "Date", "Comment"]);
+            header.set_color(SemanticColor::Importance(Importance::Header));
+            let mut table = Table::new(header);
+            for entry in store.findings.iter() {
+                table.add_row(Row::from_strings(&[
+                    entry.fingerprint.chars().take(12).collect(),
+                    entry.status.to_string(),
+                    entry.author.clone(),
+                    entry.date.clone(),
+                    entry.comment.clone(),
+                ]));
+            }
+            table.print();
+        }
+    }
+    Ok(())
+}
+
+fn run_telemetry_command(opts: &Scout, command: &TelemetryCommand) -> Result<()> {
+    match command {
+        TelemetryCommand::Status => {
+            // Outside of a project there is no configuration or blockchain to show
+            let metadata = Project::get_metadata(&opts.manifest_path).ok();
+            let enabled_by_config = match &metadata {
+                Some(metadata) => ProfileConfig::telemetry_enabled(
+                    ProfileConfig::read_project_config(metadata)?.as_ref(),
+                ),
+                None => true,
+            };
+            let blockchain = metadata.as_ref().map(BlockChain::get_blockchain_dependency);
+
+            let status = TelemetryStatus::get(opts.no_telemetry, enabled_by_config);
+            println!("Telemetry and update checks are {}.", status);
+            if status.is_enabled() {
+                println!("Each run sends the following report:");
+                println!(
+                    "{}",
+                    to_string_pretty(&TelemetryClient::preview(blockchain, ClientType::Cli))?
+                );
+            }
+        }
+        TelemetryCommand::Enable => {
+            TelemetryClient::enable()?;
+            print_info("Telemetry enabled. A new user ID will be assigned on the next run.");
+        }
+        TelemetryCommand::Disable => {
+            let previous_user_id = TelemetryClient::disable()?;
+            print_info("Telemetry and update checks disabled. The stored user ID was removed.");
+            if let Some(user_id) = previous_user_id {
+                print_info(&format!(
+                    "To delete the data already sent, visit {}",
+                    TelemetryClient::delete_data_url(&user_id)
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Analysis {
+    pub inside_vscode: bool,
+    pub project_info: Project,
+    pub detectors_info: LintStore,
+    pub output_format: Vec<OutputFormat>,
+    pub fail_on: Option<Severity>,
+    pub template_dir: Option<PathBuf>,
+    pub crates: HashMap<String, bool>,
+    pub raw_findings: Vec<Finding>,
+    // Left after suppressions, path filters, the baseline and the diff
+    pub findings: Vec<Finding>,
+    // Every cargo message, the findings are filtered once triaged
+    pub output_string_vscode: String,
+    pub suppressions: Suppressions,
+    pub suppressed_findings: Vec<SuppressedFinding>,
+    pub excluded_by_path: HashMap<String, u32>,
+    pub baseline_comparison: Option<BaselineComparison>,
+    pub pre_existing_findings: Vec<PreExistingFinding>,
+}
+
 struct RunInfo {
     pub inside_vscode: bool,
     pub project_info: Project,
@@ -162,12 +475,38 @@ struct RunInfo {
     pub filtered_detectors: Vec<String>,
     pub detectors_info: LintStore,
     pub detectors_paths: Vec<PathBuf>,
+    pub chain_runs: Vec<ChainRun>,
+    pub driver_path: PathBuf,
     pub output_format: Vec<OutputFormat>,
     pub toolchain: String,
+    pub fail_on: Option<Severity>,
+    pub path_filter: PathFilter,
+    pub require_justification: bool,
+    pub template_dir: Option<PathBuf>,
 }
 
-pub fn run_scout(mut opts: Scout) -> Result<ScoutResult> {
-    let either = prepare_scout_input(&mut opts)?;
+// Packages of one blockchain, analyzed with the `rust` detectors plus those of
+// the blockchain.
+struct ChainRun {
+    pub packages: Vec<String>,
+    // Whether cargo needs `-p` to analyze only `packages`
+    pub select_packages: bool,
+    // Cargo arguments, including the build target of the blockchain
+    pub args: Vec<String>,
+    pub detectors_paths: Vec<PathBuf>,
+}
+
+pub fn run_scout(opts: Scout) -> Result<ScoutResult> {
+    let events = EventStream::new(opts.events);
+    let result = run_analysis(opts, &events);
+    if let Err(e) = &result {
+        events.error(e);
+    }
+    result
+}
+
+fn run_analysis(mut opts: Scout, events: &EventStream) -> Result<ScoutResult> {
+    let either = prepare_scout_input(&mut opts, events)?;
 
     let info = match either {
         EitherInfoOrScoutResult::Info(run_info) => run_info,
@@ -176,6 +515,192 @@ pub fn run_scout(mut opts: Scout) -> Result<ScoutResult> {
         }
     };
 
+    let Analysis {
+        inside_vscode,
+        project_info,
+        detectors_info,
+        output_format,
+        fail_on,
+        template_dir,
+        crates,
+        raw_findings,
+        findings: console_findings,
+        output_string_vscode,
+        suppressions,
+        suppressed_findings,
+        excluded_by_path,
+        baseline_comparison,
+        pre_existing_findings,
+    } = analyze(&opts, info, events)?;
+
+    // The terminal UI can triage findings, so it runs before the statuses are
+    // applied
+    if opts.tui && !inside_vscode {
+        browse_findings(
+            &console_findings,
+            &detectors_info,
+            &project_info.workspace_root,
+        )
+        .map_err(ScoutError::TuiFailed)?;
+    }
+
+    // Triaged findings keep their status in the reports, but are left out of
+    // the console output and the failure threshold
+    let triage =
+        TriageStore::load(&project_info.workspace_root).map_err(ScoutError::TriageFailed)?;
+    let report_findings = console_findings.clone();
+    let (console_findings, triaged_findings) = triage.apply(console_findings);
+
+    let failing_findings = count_failing_findings(&console_findings, &detectors_info, fail_on);
+
+    // The editor gets the same findings as the console
+    let output_string_vscode = if inside_vscode {
+        filter_output(&output_string_vscode, &console_findings, &detectors_info)
+    } else {
+        output_string_vscode
+    };
+
+    // Generate report
+    let mut summary = None;
+    if inside_vscode {
+        std::io::stdout()
+            .lock()
+            .write_all(output_string_vscode.as_bytes())
+            .with_context(|| "Failed to write stdout content")?;
+    } else {
+        if events.is_enabled() {
+            for finding in report_findings.iter() {
+                events.emit(Event::Finding(FindingEvent::new(
+                    finding,
+                    &detectors_info,
+                    &triage,
+                )));
+            }
+            summary = Some(SummaryEvent {
+                findings: console_findings.len(),
+                by_severity: count_by_severity(&console_findings, &detectors_info),
+                suppressed: suppressed_findings.len(),
+                excluded_by_path: excluded_by_path.values().sum(),
+                known_from_baseline: baseline_comparison
+                    .as_ref()
+                    .map_or(0, |comparison| comparison.known_findings),
+                pre_existing: pre_existing_findings.len(),
+                triaged: triaged_findings.len(),
+                failed_crates: failed_crates(&crates),
+                threshold_exceeded: fail_on.is_some() && failing_findings > 0,
+            });
+        } else {
+            if !opts.tui {
+                crate::scout::output::console::render_report(
+                    &console_findings,
+                    &crates,
+                    &detectors_info,
+                )?;
+            }
+            print_unjustified_allows(&suppressions.unjustified());
+            print_suppressed_summary(&suppressed_findings);
+            print_excluded_summary(&excluded_by_path);
+            if let Some(comparison) = &baseline_comparison {
+                print_baseline_summary(comparison);
+            }
+            print_pre_existing_summary(pre_existing_findings.len());
+            print_triaged_summary(&triaged_findings);
+        }
+        Report::generate(
+            &report_findings,
+            raw_findings,
+            &crates,
+            project_info,
+            &detectors_info,
+            opts.output_path.clone(),
+            opts.output_dir.clone(),
+            template_dir,
+            &output_format,
+            &triage,
+            OmittedFindings {
+                compared_to_baseline: baseline_comparison.is_some(),
+                fixed_since_baseline: baseline_comparison
+                    .map(|comparison| comparison.fixed_findings)
+                    .unwrap_or_default(),
+                excluded_by_path,
+                suppressed: suppressed_findings,
+                pre_existing: if opts.show_pre_existing {
+                    pre_existing_findings
+                } else {
+                    Vec::new()
+                },
+            },
+            opts.offline,
+        )?;
+    }
+
+    if let Some(summary) = summary {
+        events.emit(Event::Summary(summary));
+    }
+
+    if let Some(path) = opts.get_fail_path()
+        && failing_findings == 0
+    {
+        let _ = std::fs::remove_file(path);
+    }
+
+    let mut result = ScoutResult::new(console_findings, output_string_vscode);
+    result.threshold_exceeded = fail_on.is_some() && failing_findings > 0;
+    result.build_failed = crates.values().any(|success| !success);
+    Ok(result)
+}
+
+// The analysis behind `ScoutBuilder::run`. Unlike `run_scout`, it doesn't
+// write reports, render the console output or start the terminal UI.
+pub(crate) fn analyze_project(mut opts: Scout) -> Result<AnalysisResult> {
+    let _quiet = quiet_scope(opts.quiet);
+    let events = EventStream::new(None);
+    let info = match prepare_scout_input(&mut opts, &events)? {
+        EitherInfoOrScoutResult::Info(run_info) => run_info,
+        EitherInfoOrScoutResult::ScoutResult(_) => {
+            bail!("The options don't request an analysis")
+        }
+    };
+    let analysis = analyze(&opts, info, &events)?;
+
+    let triage = TriageStore::load(&analysis.project_info.workspace_root)
+        .map_err(ScoutError::TriageFailed)?;
+    let (untriaged_findings, _) = triage.apply(analysis.findings.clone());
+    let failing_findings = count_failing_findings(
+        &untriaged_findings,
+        &analysis.detectors_info,
+        analysis.fail_on,
+    );
+
+    let report = Report::build(
+        &analysis.findings,
+        &analysis.crates,
+        &analysis.project_info,
+        &analysis.detectors_info,
+        &triage,
+        OmittedFindings {
+            compared_to_baseline: analysis.baseline_comparison.is_some(),
+            fixed_since_baseline: analysis
+                .baseline_comparison
+                .map(|comparison| comparison.fixed_findings)
+                .unwrap_or_default(),
+            excluded_by_path: analysis.excluded_by_path,
+            suppressed: analysis.suppressed_findings,
+            pre_existing: analysis.pre_existing_findings,
+        },
+    )?;
+
+    Ok(AnalysisResult {
+        report,
+        detectors_info: analysis.detectors_info,
+        crates: analysis.crates,
+        threshold_exceeded: analysis.fail_on.is_some() && failing_findings > 0,
+    })
+}
+
+// Runs the detectors and applies every filter, without showing or writing the
+// results.
+fn analyze(opts: &Scout, info: RunInfo, events: &EventStream) -> Result<Analysis> {
     let RunInfo {
         inside_vscode,
         project_info,
@@ -183,34 +708,151 @@ pub fn run_scout(mut opts: Scout) -> Result<ScoutResult> {
         filtered_detectors,
         detectors_info,
         detectors_paths,
+        chain_runs,
+        driver_path,
         output_format,
         toolchain,
+        fail_on,
+        path_filter,
+        require_justification,
+        template_dir,
     } = info;
 
-    // Run dylint
-    let (_, stdout) = run_dylint(&toolchain, &detectors_paths, &opts, inside_vscode)
+    let cache = get_findings_cache(
+        opts,
+        &metadata,
+        &project_info,
+        &detectors_paths,
+        &chain_runs
+            .iter()
+            .flat_map(|run| run.args.iter().cloned())
+            .collect::<Vec<_>>(),
+        &toolchain,
+    );
+    let lookup = cache
+        .as_ref()
+        .map(|cache| cache.lookup(&project_info.packages));
+    if let Some(lookup) = &lookup
+        && !lookup.cached_packages.is_empty()
+    {
+        print_info(&format!(
+            "Reusing cached findings for {} of {} packages.",
+            lookup.cached_packages.len(),
+            project_info.packages.len()
+        ));
+    }
+
+    let detector_names = HashSet::from_iter(filtered_detectors.iter().cloned());
+    if events.is_enabled()
+        && let Some(lookup) = &lookup
+        && !lookup.cached_packages.is_empty()
+    {
+        let cached_findings = lookup
+            .cached_output
+            .iter()
+            .map(|value| Finding::parse(value.clone()))
+            .collect::<Result<Vec<_>>>()
+            .map_err(ScoutError::ParseOutputFailed)?;
+        emit_crates_started(events, &lookup.cached_packages, true);
+        emit_crates_finished(
+            events,
+            &lookup.cached_packages,
+            true,
+            &cached_findings,
+            &metadata,
+            &detector_names,
+        )?;
+    }
+
+    // Run dylint once per blockchain, only over the packages that changed when
+    // some are cached
+    let mut raw_findings_string = String::new();
+    for run in chain_runs.iter() {
+        let stale_packages = run
+            .packages
+            .iter()
+            .filter(|name| {
+                lookup.as_ref().is_none_or(|lookup| {
+                    lookup
+                        .stale_packages
+                        .iter()
+                        .any(|package| &package.name == *name)
+                })
+            })
+            .collect::<Vec<_>>();
+        if stale_packages.is_empty() {
+            continue;
+        }
+
+        let mut dylint_opts = opts.clone();
+        dylint_opts.args = run.args.clone();
+        if run.select_packages || stale_packages.len() < run.packages.len() {
+            let selection = stale_packages
+                .iter()
+                .flat_map(|name| ["-p".to_string(), name.to_string()]);
+            dylint_opts.args.splice(0..0, selection);
+        }
+        // The driver hands back the cargo messages once it exits, so the
+        // packages of a run start and finish together
+        let packages = project_info
+            .packages
+            .iter()
+            .filter(|package| stale_packages.contains(&&package.name))
+            .cloned()
+            .collect::<Vec<_>>();
+        emit_crates_started(events, &packages, false);
+        let (_, stdout) = run_dylint(
+            &toolchain,
+            &driver_path,
+            &run.detectors_paths,
+            &dylint_opts,
+            inside_vscode,
+        )
         .map_err(ScoutError::RunDylintFailed)?;
+        let output = temp_file_to_string(&stdout)?;
+        if events.is_enabled() {
+            let findings = output_to_findings(&output).map_err(ScoutError::ParseOutputFailed)?;
+            emit_crates_finished(
+                events,
+                &packages,
+                false,
+                &findings,
+                &metadata,
+                &detector_names,
+            )?;
+        }
+        append_output(&mut raw_findings_string, &output);
+    }
 
-    let mut raw_findings_string = temp_file_to_string(&stdout)?;
+    if let (Some(cache), Some(lookup)) = (&cache, &lookup) {
+        update_findings_cache(
+            cache,
+            lookup,
+            &raw_findings_string,
+            &project_info,
+            &metadata,
+        )?;
+        for value in lookup.cached_output.iter() {
+            append_output(&mut raw_findings_string, &value.to_string());
+        }
+    }
 
-    let raw_findings = output_to_json(&raw_findings_string)
-        .into_iter()
-        .map(Finding::new)
-        .collect::<Vec<_>>();
+    let raw_findings =
+        output_to_findings(&raw_findings_string).map_err(ScoutError::ParseOutputFailed)?;
     let crates = get_crates(&raw_findings, &project_info.packages, &metadata)?;
-    let detector_names = HashSet::from_iter(filtered_detectors.iter().cloned());
 
     let raw_findings = set_severity(
         &mut raw_findings_string,
         &raw_findings,
         &detector_names,
         &detectors_info,
-    );
+    )
+    .map_err(ScoutError::ParseOutputFailed)?;
 
     let findings = raw_findings
         .iter()
         .filter(|&x| x.is_scout_finding(&detector_names))
-        .cloned()
+        .map(|finding| finding.clone().fingerprinted(&project_info.workspace_root))
         .collect::<Vec<_>>();
 
     if crates.is_empty() && !inside_vscode {
@@ -219,7 +861,7 @@ pub fn run_scout(mut opts: Scout) -> Result<ScoutResult> {
             .red()
             .text_str("Nothing was analyzed. Check your build system for errors.")
             .print();
-        println!("{}", string);
+        print_line(&string);
         return Err(anyhow!(
             "Nothing was analyzed. Check your build system for errors."
         ));
@@ -245,32 +887,287 @@ pub fn run_scout(mut opts: Scout) -> Result<ScoutResult> {
         (successful_findings, raw_findings_string)
     };
 
-    // Generate report
-    if inside_vscode {
-        std::io::stdout()
-            .lock()
-            .write_all(output_string_vscode.as_bytes())
-            .with_context(|| "Failed to write stdout content")?;
+    // Honor `scout_allow` attributes regardless of the loaded detectors
+    let mut suppressions = Suppressions::new(&project_info.workspace_root, require_justification);
+    let (console_findings, suppressed_findings) = suppressions.apply(console_findings);
+
+    let (console_findings, excluded_by_path) = path_filter.apply(console_findings);
+
+    // Save the current findings before the baseline hides any of them
+    if let Some(path) = &opts.write_baseline {
+        Baseline::from_findings(&console_findings)
+            .save(path)
+            .map_err(ScoutError::BaselineFailed)?;
+    }
+
+    let (console_findings, baseline_comparison) = if let Some(path) = &opts.baseline {
+        let comparison = Baseline::load(path)
+            .map_err(ScoutError::BaselineFailed)?
+            .compare(console_findings);
+        (comparison.new_findings.clone(), Some(comparison))
     } else {
-        crate::scout::output::console::render_report(&console_findings, &crates, &detectors_info)?;
-        Report::generate(
-            &console_findings,
-            raw_findings,
-            &crates,
-            project_info,
-            &detectors_info,
-            opts.output_path.clone(),
-            &output_format,
-        )?;
+        (console_findings, None)
+    };
+
+    // Applied after the baseline, so findings outside the diff don't show up as fixed
+    let (console_findings, pre_existing_findings) = if let Some(base) = &opts.diff_base {
+        DiffFilter::new(&project_info.workspace_root, base)
+            .map_err(ScoutError::DiffBaseFailed)?
+            .apply(console_findings)
+    } else {
+        (console_findings, Vec::new())
+    };
+
+    Ok(Analysis {
+        inside_vscode,
+        project_info,
+        detectors_info,
+        output_format,
+        fail_on,
+        template_dir,
+        crates,
+        raw_findings,
+        findings: console_findings,
+        output_string_vscode,
+        suppressions,
+        suppressed_findings,
+        excluded_by_path,
+        baseline_comparison,
+        pre_existing_findings,
+    })
+}
+
+fn get_findings_cache(
+    opts: &Scout,
+    metadata: &Metadata,
+    project_info: &Project,
+    detectors_paths: &[PathBuf],
+    args: &[String],
+    toolchain: &str,
+) -> Option<FindingsCache> {
+    if opts.no_cache || !FindingsCache::supports_args(&opts.args) {
+        return None;
+    }
+    match FindingsCache::new(
+        metadata,
+        &project_info.packages,
+        detectors_paths,
+        toolchain,
+        args,
+    ) {
+        Result::Ok(cache) => Some(cache),
+        Err(e) => {
+            // The cache is an optimization, so the analysis goes on without it
+            print_warning(&format!("Findings cache disabled: {}", e));
+            None
+        }
     }
+}
 
-    if let Some(path) = opts.get_fail_path()
-        && console_findings.is_empty()
-    {
-        let _ = std::fs::remove_file(path);
+// Stores the output of the packages that were analyzed successfully in this run.
+fn update_findings_cache(
+    cache: &FindingsCache,
+    lookup: &CacheLookup,
+    output: &str,
+    project_info: &Project,
+    metadata: &Metadata,
+) -> Result<()> {
+    let findings = output_to_findings(output)?;
+    let crates = get_crates(&findings, &project_info.packages, metadata)?;
+    for package in lookup.stale_packages.iter() {
+        if crates.get(&normalize_crate_name(&package.name)) != Some(&true) {
+            continue;
+        }
+        if let Err(e) = cache.store(package, &findings) {
+            print_warning(&format!(
+                "Failed to cache the findings of {}: {}",
+                package.name, e
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Joins the cargo messages of several runs, one per line.
+fn append_output(output: &mut String, messages: &str) {
+    if !output.is_empty() && !output.ends_with('\n') {
+        output.push('\n');
+    }
+    output.push_str(messages);
+}
+
+fn emit_crates_started(events: &EventStream, packages: &[Package], cached: bool) {
+    for package in packages {
+        events.emit(Event::CrateStarted {
+            name: package.name.clone(),
+            cached,
+        });
+    }
+}
+
+fn emit_crates_finished(
+    events: &EventStream,
+    packages: &[Package],
+    cached: bool,
+    findings: &[Finding],
+    metadata: &Metadata,
+    detector_names: &HashSet<String>,
+) -> Result<()> {
+    let crates = get_crates(findings, packages, metadata)?;
+    for package in packages.iter() {
+        let name = normalize_crate_name(&package.name);
+        events.emit(Event::CrateFinished {
+            name: package.name.clone(),
+            success: crates.get(&name).copied().unwrap_or(true),
+            cached,
+            findings: findings
+                .iter()
+                .filter(|finding| {
+                    finding.is_scout_finding(detector_names)
+                        && normalize_crate_name(&finding.package()) == name
+                })
+                .count(),
+        });
+    }
+    Ok(())
+}
+
+fn count_by_severity(
+    findings: &[Finding],
+    detectors_info: &LintStore,
+) -> BTreeMap<Severity, usize> {
+    let mut counts = BTreeMap::new();
+    for severity in findings.iter().filter_map(|finding| {
+        detectors_info
+            .find_by_id(&finding.code())
+            .and_then(|detector| Severity::from_lint_severity(&detector.severity))
+    }) {
+        *counts.entry(severity).or_insert(0) += 1;
+    }
+    counts
+}
+
+fn failed_crates(crates: &HashMap<String, bool>) -> Vec<String> {
+    let mut failed = crates
+        .iter()
+        .filter(|(_, success)| !**success)
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    failed.sort();
+    failed
+}
+
+// Counts the findings that should make the analysis fail. Without a threshold,
+// or when a detector has no known severity, every finding counts.
+fn count_failing_findings(
+    findings: &[Finding],
+    detectors_info: &LintStore,
+    fail_on: Option<Severity>,
+) -> usize {
+    let Some(threshold) = fail_on else {
+        return findings.len();
+    };
+    findings
+        .iter()
+        .filter(|finding| {
+            detectors_info
+                .find_by_id(&finding.code())
+                .and_then(|detector| Severity::from_lint_severity(&detector.severity))
+                .is_none_or(|severity| severity.is_at_least(threshold))
+        })
+        .count()
+}
+
+fn print_unjustified_allows(unjustified: &[&UnjustifiedAllow]) {
+    for allow in unjustified {
+        print_warning(&format!(
+            "Ignoring `scout_allow` without a reason at {}:{}. This project requires one, e.g. `#[scout_allow(detector, reason = \"...\")]`.",
+            allow.file_path, allow.line
+        ));
+    }
+}
+
+fn print_suppressed_summary(suppressed: &[SuppressedFinding]) {
+    if suppressed.is_empty() {
+        return;
+    }
+    let detectors = count_by_detector(suppressed)
+        .into_iter()
+        .map(|(detector, count)| format!("{} {}", count, detector))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let string = OutputFormatter::new()
+        .fg()
+        .yellow()
+        .text_str(
+            format!(
+                "{} findings suppressed by scout_allow ({}).",
+                suppressed.len(),
+                detectors
+            )
+            .as_str(),
+        )
+        .print();
+    println!("{}", string);
+}
+
+fn print_excluded_summary(excluded_by_path: &HashMap<String, u32>) {
+    let excluded = excluded_by_path.values().sum::<u32>();
+    if excluded == 0 {
+        return;
+    }
+    let string = OutputFormatter::new()
+        .fg()
+        .yellow()
+        .text_str(format!("{} findings excluded by path filters.", excluded).as_str())
+        .print();
+    println!("{}", string);
+}
+
+fn print_pre_existing_summary(pre_existing: usize) {
+    if pre_existing == 0 {
+        return;
     }
+    let string = OutputFormatter::new()
+        .fg()
+        .yellow()
+        .text_str(format!("{} pre-existing findings outside the diff.", pre_existing).as_str())
+        .print();
+    println!("{}", string);
+}
+
+fn print_triaged_summary(triaged: &[(Finding, TriageEntry)]) {
+    if triaged.is_empty() {
+        return;
+    }
+    let statuses = count_by_status(triaged)
+        .into_iter()
+        .map(|(status, count)| format!("{} {}", count, status))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let string = OutputFormatter::new()
+        .fg()
+        .yellow()
+        .text_str(format!("{} triaged findings hidden ({}).", triaged.len(), statuses).as_str())
+        .print();
+    println!("{}", string);
+}
 
-    Ok(ScoutResult::new(console_findings, output_string_vscode))
+fn print_baseline_summary(comparison: &BaselineComparison) {
+    let string = OutputFormatter::new()
+        .fg()
+        .green()
+        .text_str(
+            format!(
+                "Baseline: {} new, {} known (hidden), {} fixed since baseline.",
+                comparison.new_findings.len(),
+                comparison.known_findings,
+                comparison.fixed_findings.len()
+            )
+            .as_str(),
+        )
+        .print();
+    println!("{}", string);
 }
 
 fn set_severity(
@@ -278,7 +1175,7 @@ fn set_severity(
     raw_findings: &[Finding],
     detector_names: &HashSet<String>,
     detectors_info: &LintStore,
-) -> Vec<Finding> {
+) -> Result<Vec<Finding>> {
     for finding in raw_findings.iter() {
         if finding.is_scout_finding(detector_names)
             && let Some(detector) = detectors_info.find_by_id(&finding.code())
@@ -293,8 +1190,37 @@ fn set_severity(
             }
         }
     }
-    output_to_json(raw_findings_string)
-        .into_iter()
-        .map(Finding::new)
-        .collect::<Vec<_>>()
+    output_to_findings(raw_findings_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::set_severity;
+    use crate::{
+        config::apply_severity_overrides,
+        scout::{core::findings::output_to_findings, output::report::Severity},
+        util::detectors_info::{LintInfo, LintStore},
+    };
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn severity_tags_use_the_overridden_severities() {
+        let mut detectors_info = LintStore::new();
+        detectors_info.insert(LintInfo {
+            id: "unsafe_unwrap".to_string(),
+            severity: "Minor".to_string(),
+            ..Default::default()
+        });
+        apply_severity_overrides(
+            &mut detectors_info,
+            &HashMap::from([("unsafe_unwrap".to_string(), Severity::Critical)]),
+        );
+
+        let mut output = r#"{"reason":"compiler-message","message":{"message":"Unsafe usage of `unwrap`","code":{"code":"unsafe_unwrap"}}}"#.to_string();
+        let findings = output_to_findings(&output).unwrap();
+        let detectors = HashSet::from(["unsafe-unwrap".to_string()]);
+        let findings = set_severity(&mut output, &findings, &detectors, &detectors_info).unwrap();
+
+        assert_eq!(findings[0].message(), "[CRITICAL] Unsafe usage of `unwrap`");
+    }
 }
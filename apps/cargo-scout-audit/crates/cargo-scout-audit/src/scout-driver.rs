@@ -5,11 +5,24 @@ use crate::{
     util::build_and_run::PackageToBuild,
 };
 use anyhow::{Result, anyhow};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+pub fn build_scout_driver(toolchain: &str, scout_source: Option<&PathBuf>) -> Result<PathBuf> {
+    let mut pkg = match scout_source {
+        Some(root) => PackageToBuild::new_local(root.clone()),
+        None => PackageToBuild::new_remote(SCOUT_REPO, SCOUT_BRANCH, "scout-driver"),
+    };
+    pkg.build_message = "Building scout-driver".to_string();
+    pkg.build_error_message = "Failed to build scout-driver".to_string();
+    pkg.toolchain = Some(format!("+{}", toolchain.trim_start_matches('+')));
+    pkg.internal_path = Some("apps/cargo-scout-audit/crates/scout-driver".into());
+    pkg.build_executable(Some("scout-driver"), "scout-driver")
+}
 
 //#[tracing::instrument(name = "RUN DYLINT", skip_all)]
 pub fn run_dylint(
     toolchain: &str,
+    driver_path: &Path,
     detectors_paths: &[PathBuf],
     opts: &Scout,
     inside_vscode: bool,
@@ -20,19 +33,11 @@ pub fn run_dylint(
         inside_vscode,
     };
 
-    let mut pkg = if let Some(root) = &opts.scout_source {
-        PackageToBuild::new_local(root.clone())
-    } else {
-        PackageToBuild::new_remote(SCOUT_REPO, SCOUT_BRANCH, "scout-driver")
-    };
-    pkg.build_message = "Building scout-driver".to_string();
-    pkg.build_error_message = "Failed to build scout-driver".to_string();
-    pkg.toolchain = Some(format!("+{}", toolchain.trim_start_matches('+')));
-    pkg.internal_path = Some("apps/cargo-scout-audit/crates/scout-driver".into());
-    let path = pkg.build_executable(Some("scout-driver"), "scout-driver")?;
-
-    let output =
-        crate::interop::subprocess::run_subprocess::<_, ScoutOutput>(toolchain, &path, &input)?;
+    let output = crate::interop::subprocess::run_subprocess::<_, ScoutOutput>(
+        toolchain,
+        driver_path,
+        &input,
+    )?;
 
     match output.result {
         Ok(x) => Ok((x.success, x.output_file_path.into())),
@@ -0,0 +1,165 @@
+use crate::scout::{finding::Finding, output::utils::write_to_file};
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+pub const BASELINE_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct BaselineFinding {
+    pub fingerprint: String,
+    pub vulnerability_id: String,
+    pub package: String,
+    pub file_path: String,
+    pub code_snippet: String,
+    pub error_message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Baseline {
+    pub version: u32,
+    pub scout_version: String,
+    pub findings: Vec<BaselineFinding>,
+}
+
+#[derive(Debug, Default)]
+pub struct BaselineComparison {
+    pub new_findings: Vec<Finding>,
+    pub known_findings: usize,
+    pub fixed_findings: Vec<BaselineFinding>,
+}
+
+impl BaselineFinding {
+    pub fn from_finding(finding: &Finding) -> Self {
+        BaselineFinding {
+            fingerprint: finding.fingerprint().to_string(),
+            vulnerability_id: finding.code(),
+            package: finding.package(),
+            file_path: finding.file_name().unwrap_or_default().replace('\\', "/"),
+            code_snippet: finding.snippet().to_string(),
+            error_message: finding.message(),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn from_findings(findings: &[Finding]) -> Self {
+        Baseline {
+            version: BASELINE_VERSION,
+            scout_version: env!("CARGO_PKG_VERSION").to_string(),
+            findings: findings.iter().map(BaselineFinding::from_finding).collect(),
+        }
+    }
+
+    #[tracing::instrument(name = "LOADING BASELINE", level = "debug", fields(path = %path.display()))]
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline file: {}", path.display()))?;
+        let baseline: Baseline = serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse baseline file: {}", path.display()))?;
+        if baseline.version > BASELINE_VERSION {
+            bail!(
+                "Baseline file version {} is not supported by this version of scout (max {})",
+                baseline.version,
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    #[tracing::instrument(name = "SAVING BASELINE", level = "debug", skip(self), fields(path = %path.display()))]
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        write_to_file(path, json.as_bytes())
+            .with_context(|| format!("Failed to write baseline file: {}", path.display()))
+    }
+
+    // Splits the current findings into new and known ones. A fingerprint that
+    // appears N times in the baseline only absorbs N current findings, so a
+    // duplicated vulnerability is still reported.
+    pub fn compare(&self, findings: Vec<Finding>) -> BaselineComparison {
+        let mut remaining: HashMap<&str, Vec<&BaselineFinding>> = HashMap::new();
+        for known in self.findings.iter() {
+            remaining
+                .entry(known.fingerprint.as_str())
+                .or_default()
+                .push(known);
+        }
+
+        let mut comparison = BaselineComparison::default();
+        for finding in findings {
+            match remaining.get_mut(finding.fingerprint()).and_then(Vec::pop) {
+                Some(_) => comparison.known_findings += 1,
+                None => comparison.new_findings.push(finding),
+            }
+        }
+
+        // Keep the baseline order for the findings that disappeared.
+        comparison.fixed_findings = self
+            .findings
+            .iter()
+            .rev()
+            .filter(|known| {
+                remaining
+                    .get_mut(known.fingerprint.as_str())
+                    .and_then(Vec::pop)
+                    .is_some()
+            })
+            .cloned()
+            .collect();
+        comparison.fixed_findings.reverse();
+
+        comparison
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn finding(code: &str, file_name: &str) -> Finding {
+        Finding::new(json!({
+            "reason": "compiler-message",
+            "target": { "name": "token" },
+            "message": {
+                "message": "message",
+                "code": { "code": code },
+                "spans": [{ "file_name": file_name, "byte_start": 0, "byte_end": 0 }],
+            },
+        }))
+        .fingerprinted(Path::new("/nonexistent"))
+    }
+
+    #[test]
+    fn compare_reports_new_and_fixed_findings() {
+        let baseline = Baseline::from_findings(&[
+            finding("unsafe_unwrap", "src/lib.rs"),
+            finding("unsafe_expect", "src/lib.rs"),
+        ]);
+
+        let comparison = baseline.compare(vec![
+            finding("unsafe_unwrap", "src/lib.rs"),
+            finding("unsafe_unwrap", "src/lib.rs"),
+            finding("divide_before_multiply", "src/lib.rs"),
+        ]);
+
+        assert_eq!(comparison.known_findings, 1);
+        assert_eq!(
+            comparison
+                .new_findings
+                .iter()
+                .map(Finding::code)
+                .collect::<Vec<_>>(),
+            ["unsafe_unwrap", "divide_before_multiply"]
+        );
+        assert_eq!(comparison.fixed_findings.len(), 1);
+        assert_eq!(
+            comparison.fixed_findings[0].vulnerability_id,
+            "unsafe_expect"
+        );
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{
+    cli_args::selects_packages, digest::SOURCE_DIGEST, scout::finding::Finding,
+    scout::output::report::Package,
+};
+use anyhow::{Context, Result};
+use cargo_metadata::Metadata;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+const CACHE_VERSION: u32 = 1;
+
+// Added to the cargo messages read back from the cache, so the report can tell
+// them apart from the ones produced by this run.
+pub const FROM_CACHE_FIELD: &str = "scout_cache";
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub package_id: String,
+    pub source_digest: String,
+    pub detectors_digest: String,
+    pub toolchain: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    key: CacheKey,
+    output: Vec<Value>,
+}
+
+// Findings of previous runs, one entry per workspace member, stored under the
+// target directory. An entry is reused while the member sources (and those of
+// its local dependencies), the detectors and the toolchain are unchanged.
+pub struct FindingsCache {
+    directory: PathBuf,
+    keys: HashMap<String, CacheKey>,
+}
+
+pub struct CacheLookup {
+    // Cargo messages of the members that can be skipped
+    pub cached_output: Vec<Value>,
+    pub cached_packages: Vec<Package>,
+    pub stale_packages: Vec<Package>,
+}
+
+impl FindingsCache {
+    pub fn new(
+        metadata: &Metadata,
+        packages: &[Package],
+        detectors_paths: &[PathBuf],
+        toolchain: &str,
+        args: &[String],
+    ) -> Result<Self> {
+        let detectors_digest = detectors_digest(detectors_paths, args)?;
+        let mut sources = SourceDigests::new(metadata);
+        let mut keys = HashMap::new();
+        for package in packages {
+            keys.insert(
+                package.id.clone(),
+                CacheKey {
+                    package_id: package.id.clone(),
+                    source_digest: sources.get(&package.id)?,
+                    detectors_digest: detectors_digest.clone(),
+                    toolchain: toolchain.to_string(),
+                },
+            );
+        }
+        Ok(Self {
+            directory: metadata
+                .target_directory
+                .join("scout")
+                .join("cache")
+                .into_std_path_buf(),
+            keys,
+        })
+    }
+
+    // The cache narrows the run with `-p`, so it stays out of the way when the
+    // user selects packages themselves.
+    pub fn supports_args(args: &[String]) -> bool {
+        !selects_packages(args)
+    }
+
+    pub fn lookup(&self, packages: &[Package]) -> CacheLookup {
+        let mut ret = CacheLookup {
+            cached_output: Vec::new(),
+            cached_packages: Vec::new(),
+            stale_packages: Vec::new(),
+        };
+        for package in packages {
+            match self.load(package) {
+                Some(output) => {
+                    ret.cached_output
+                        .extend(output.into_iter().map(mark_cached));
+                    ret.cached_packages.push(package.clone());
+                }
+                None => ret.stale_packages.push(package.clone()),
+            }
+        }
+        ret
+    }
+
+    fn load(&self, package: &Package) -> Option<Vec<Value>> {
+        let key = self.keys.get(&package.id)?;
+        let contents = fs::read_to_string(self.entry_path(package)).ok()?;
+        let entry = serde_json::from_str::<CacheEntry>(&contents).ok()?;
+        (entry.version == CACHE_VERSION && &entry.key == key).then_some(entry.output)
+    }
+
+    // Saves the cargo messages that belong to `package`. Only call this for
+    // members that were analyzed successfully. Members that were not checked
+    // at all (e.g. the build stopped early) are left out.
+    pub fn store(&self, package: &Package, output: &[Finding]) -> Result<()> {
+        let output = output
+            .iter()
+            .filter(|finding| finding.package_id() == package.id)
+            .collect::<Vec<_>>();
+        if !output
+            .iter()
+            .any(|finding| finding.reason() == "compiler-artifact")
+        {
+            return Ok(());
+        }
+        let key = self
+            .keys
+            .get(&package.id)
+            .with_context(|| format!("No cache key for package {}", package.name))?;
+        let entry = CacheEntry {
+            version: CACHE_VERSION,
+            key: key.clone(),
+            output: output.iter().map(|finding| finding.json()).collect(),
+        };
+        fs::create_dir_all(&self.directory)
+            .with_context(|| format!("Failed to create {}", self.directory.display()))?;
+        fs::write(self.entry_path(package), serde_json::to_string(&entry)?)
+            .with_context(|| format!("Failed to write the cache entry of {}", package.name))?;
+        Ok(())
+    }
+
+    fn entry_path(&self, package: &Package) -> PathBuf {
+        let id_hash = format!("{:x}", Sha256::digest(package.id.as_bytes()));
+        self.directory
+            .join(format!("{}-{}.json", package.name, &id_hash[..16]))
+    }
+}
+
+fn mark_cached(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.insert(FROM_CACHE_FIELD.to_string(), Value::Bool(true));
+    }
+    value
+}
+
+// The arguments are part of the digest because they change what gets
+// compiled (features, target, profile).
+fn detectors_digest(detectors_paths: &[PathBuf], args: &[String]) -> Result<String> {
+    let mut paths = detectors_paths.to_vec();
+    paths.sort();
+    let mut hash = Sha256::new();
+    hash.update(SOURCE_DIGEST.as_bytes());
+    for path in paths {
+        let contents =
+            fs::read(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+        hash.update(Sha256::digest(&contents));
+    }
+    for arg in args {
+        hash.update(arg.as_bytes());
+        hash.update([0]);
+    }
+    Ok(format!("{:x}", hash.finalize()))
+}
+
+// Digests of the local packages, each one covering its own files, the lock
+// file and the digests of the local packages it depends on.
+struct SourceDigests<'a> {
+    metadata: &'a Metadata,
+    lock_file: Option<Vec<u8>>,
+    digests: HashMap<String, String>,
+    in_progress: HashSet<String>,
+}
+
+impl<'a> SourceDigests<'a> {
+    fn new(metadata: &'a Metadata) -> Self {
+        Self {
+            metadata,
+            lock_file: fs::read(metadata.workspace_root.join("Cargo.lock")).ok(),
+            digests: HashMap::new(),
+            in_progress: HashSet::new(),
+        }
+    }
+
+    fn get(&mut self, package_id: &str) -> Result<String> {
+        if let Some(digest) = self.digests.get(package_id) {
+            return Ok(digest.clone());
+        }
+        let package = self
+            .metadata
+            .packages
+            .iter()
+            .find(|package| package.id.repr == package_id)
+            .with_context(|| format!("Package ID '{package_id}' not found in the workspace"))?;
+        self.in_progress.insert(package_id.to_string());
+
+        let mut hash = Sha256::new();
+        if let Some(lock_file) = &self.lock_file {
+            hash.update(Sha256::digest(lock_file));
+        }
+        if let Some(root) = package.manifest_path.parent() {
+            let target_directory = self.metadata.target_directory.as_std_path();
+            hash_directory(
+                &mut hash,
+                root.as_std_path(),
+                root.as_std_path(),
+                target_directory,
+            )?;
+        }
+
+        // Dev-dependencies can form cycles, so packages being hashed are skipped
+        let mut dependencies = self.local_dependencies(package_id);
+        dependencies.sort();
+        for dependency in dependencies {
+            if !self.in_progress.contains(&dependency) {
+                hash.update(self.get(&dependency)?.as_bytes());
+            }
+        }
+
+        self.in_progress.remove(package_id);
+        let digest = format!("{:x}", hash.finalize());
+        self.digests.insert(package_id.to_string(), digest.clone());
+        Ok(digest)
+    }
+
+    fn local_dependencies(&self, package_id: &str) -> Vec<String> {
+        let Some(resolve) = &self.metadata.resolve else {
+            return Vec::new();
+        };
+        resolve
+            .nodes
+            .iter()
+            .find(|node| node.id.repr == package_id)
+            .map(|node| {
+                node.dependencies
+                    .iter()
+                    .filter(|id| {
+                        self.metadata
+                            .packages
+                            .iter()
+                            .any(|package| &package.id == *id && package.source.is_none())
+                    })
+                    .map(|id| id.repr.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+// Hashes every file below `directory` in a stable order. Hidden directories,
+// the target directory and nested packages (hashed on their own) are skipped.
+fn hash_directory(
+    hash: &mut Sha256,
+    root: &Path,
+    directory: &Path,
+    target_directory: &Path,
+) -> Result<()> {
+    let mut entries = fs::read_dir(directory)
+        .with_context(|| format!("Failed to read {}", directory.display()))?
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            let nested_package = path != root && path.join("Cargo.toml").exists();
+            if hidden || nested_package || path == target_directory {
+                continue;
+            }
+            hash_directory(hash, root, &path, target_directory)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            hash.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+            hash.update([0]);
+            hash.update(Sha256::digest(fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CacheKey, FROM_CACHE_FIELD, FindingsCache};
+    use crate::scout::{finding::Finding, output::report::Package};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn package(name: &str) -> Package {
+        Package {
+            name: name.to_string(),
+            id: format!("path+file:///work/{name}#0.1.0"),
+            relative_path: format!("{name}/Cargo.toml").into(),
+            absolute_path: format!("/work/{name}/Cargo.toml").into(),
+        }
+    }
+
+    fn cache(directory: &std::path::Path, packages: &[Package], source: &str) -> FindingsCache {
+        let keys = packages
+            .iter()
+            .map(|package| {
+                let key = CacheKey {
+                    package_id: package.id.clone(),
+                    source_digest: source.to_string(),
+                    detectors_digest: "detectors".to_string(),
+                    toolchain: "nightly-2025-08-07".to_string(),
+                };
+                (package.id.clone(), key)
+            })
+            .collect::<HashMap<_, _>>();
+        FindingsCache {
+            directory: directory.to_path_buf(),
+            keys,
+        }
+    }
+
+    #[test]
+    fn reuses_only_unchanged_packages() {
+        let directory = tempfile::TempDir::new().unwrap();
+        let packages = [package("token"), package("vault")];
+        let output = packages
+            .iter()
+            .flat_map(|package| {
+                ["compiler-message", "compiler-artifact"]
+                    .map(|reason| Finding::new(json!({"reason": reason, "package_id": package.id})))
+            })
+            .collect::<Vec<_>>();
+
+        let before = cache(directory.path(), &packages, "a");
+        before.store(&packages[0], &output).unwrap();
+
+        let lookup = before.lookup(&packages);
+        assert_eq!(lookup.cached_packages.len(), 1);
+        assert_eq!(lookup.stale_packages[0].name, "vault");
+        assert_eq!(lookup.cached_output.len(), 2);
+        assert_eq!(lookup.cached_output[0]["package_id"], packages[0].id);
+        assert_eq!(lookup.cached_output[0][FROM_CACHE_FIELD], true);
+
+        let after = cache(directory.path(), &packages, "b");
+        assert!(after.lookup(&packages).cached_packages.is_empty());
+    }
+
+    #[test]
+    fn steps_aside_for_package_selection() {
+        let args = |args: &[&str]| args.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        assert!(FindingsCache::supports_args(&args(&["--features", "x"])));
+        assert!(!FindingsCache::supports_args(&args(&["-p", "token"])));
+        assert!(!FindingsCache::supports_args(&args(&["--package=token"])));
+        assert!(!FindingsCache::supports_args(&args(&["-ptoken"])));
+    }
+}
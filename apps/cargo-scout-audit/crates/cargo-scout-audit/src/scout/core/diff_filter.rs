@@ -0,0 +1,189 @@
+use crate::scout::finding::Finding;
+use anyhow::{Context, Result};
+use git2::{DiffOptions, Repository};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+// A finding that was left out because it is not on a line changed since the
+// diff base.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PreExistingFinding {
+    pub vulnerability_id: String,
+    pub package: String,
+    pub file_path: String,
+    pub line: u64,
+    pub error_message: String,
+}
+
+// Keeps the findings whose primary span touches a line added or modified since
+// the diff base. Uncommitted and untracked changes count as part of the diff.
+pub struct DiffFilter {
+    workspace_root: PathBuf,
+    // Inclusive line ranges, keyed by absolute path.
+    changed_lines: HashMap<PathBuf, Vec<(u64, u64)>>,
+}
+
+impl DiffFilter {
+    pub fn new(workspace_root: &Path, base: &str) -> Result<Self> {
+        let repo = Repository::discover(workspace_root)
+            .with_context(|| "The project is not inside a git repository")?;
+        let workdir = repo
+            .workdir()
+            .with_context(|| "The git repository has no working directory")?;
+        let workdir = dunce::canonicalize(workdir).unwrap_or_else(|_| workdir.to_path_buf());
+
+        // Like `git diff base...`, so changes made on the base after the branch
+        // point are not attributed to the diff.
+        let base_commit = repo
+            .revparse_single(base)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Failed to resolve git reference '{}'", base))?;
+        let base_commit = repo
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .and_then(|head| repo.merge_base(base_commit.id(), head.id()))
+            .and_then(|id| repo.find_commit(id))
+            .unwrap_or(base_commit);
+        let base_tree = base_commit.tree()?;
+
+        let mut options = DiffOptions::new();
+        options
+            .context_lines(0)
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        let diff = repo.diff_tree_to_workdir_with_index(Some(&base_tree), Some(&mut options))?;
+
+        let mut changed_lines = HashMap::<PathBuf, Vec<(u64, u64)>>::new();
+        diff.foreach(
+            &mut |_, _| true,
+            None,
+            Some(&mut |delta, hunk| {
+                // Hunks that only remove lines leave nothing to report on
+                if let Some(path) = delta.new_file().path()
+                    && hunk.new_lines() > 0
+                {
+                    let start = u64::from(hunk.new_start());
+                    let end = start + u64::from(hunk.new_lines()) - 1;
+                    changed_lines
+                        .entry(workdir.join(path))
+                        .or_default()
+                        .push((start, end));
+                }
+                true
+            }),
+            None,
+        )?;
+
+        Ok(DiffFilter {
+            workspace_root: dunce::canonicalize(workspace_root)
+                .unwrap_or_else(|_| workspace_root.to_path_buf()),
+            changed_lines,
+        })
+    }
+
+    pub fn is_changed(&self, file_name: &str, line_start: u64, line_end: u64) -> bool {
+        self.changed_lines
+            .get(&self.workspace_root.join(file_name))
+            .is_some_and(|ranges| {
+                ranges
+                    .iter()
+                    .any(|(start, end)| *start <= line_end && line_start <= *end)
+            })
+    }
+
+    // Returns the findings on changed lines, and the pre-existing ones.
+    pub fn apply(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<PreExistingFinding>) {
+        let mut kept = Vec::new();
+        let mut pre_existing = Vec::new();
+        for finding in findings {
+            let Some((file_name, line_start, line_end)) = primary_span(&finding) else {
+                kept.push(finding);
+                continue;
+            };
+            if self.is_changed(&file_name, line_start, line_end) {
+                kept.push(finding);
+            } else {
+                pre_existing.push(PreExistingFinding {
+                    vulnerability_id: finding.code(),
+                    package: finding.package(),
+                    file_path: file_name.replace('\\', "/"),
+                    line: line_start,
+                    error_message: finding.message(),
+                });
+            }
+        }
+        (kept, pre_existing)
+    }
+}
+
+fn primary_span(finding: &Finding) -> Option<(String, u64, u64)> {
+    let span = finding.span()?;
+    Some((
+        span.file_name.clone(),
+        span.line_start as u64,
+        span.line_end as u64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DiffFilter;
+    use git2::{Repository, Signature};
+    use std::{fs, path::Path};
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("scout", "scout@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents = parent.iter().collect::<Vec<_>>();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn keeps_only_changed_lines() {
+        let directory = tempfile::TempDir::new().unwrap();
+        let root = directory.path();
+        let repo = Repository::init(root).unwrap();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/lib.rs"), "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+        commit_all(&repo, "initial");
+        let base = repo
+            .head()
+            .unwrap()
+            .peel_to_commit()
+            .unwrap()
+            .id()
+            .to_string();
+
+        fs::write(
+            root.join("src/lib.rs"),
+            "fn a() {}\nfn b() { x() }\nfn c() {}\n",
+        )
+        .unwrap();
+        fs::write(root.join("src/new.rs"), "fn d() {}\n").unwrap();
+
+        let filter = DiffFilter::new(Path::new(root), &base).unwrap();
+        assert!(filter.is_changed("src/lib.rs", 2, 2));
+        assert!(filter.is_changed("src/lib.rs", 1, 3));
+        assert!(!filter.is_changed("src/lib.rs", 3, 3));
+        assert!(filter.is_changed("src/new.rs", 1, 1));
+        assert!(!filter.is_changed("src/other.rs", 1, 1));
+    }
+}
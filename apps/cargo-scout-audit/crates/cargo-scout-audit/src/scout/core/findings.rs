@@ -1,14 +1,18 @@
 use crate::{
     scout::{self, finding::Finding},
-    util::dependencies::DependencyGraph,
+    util::{dependencies::DependencyGraph, detectors_info::LintStore},
 };
-use anyhow::Result;
+use anyhow::{Context, Result};
 use cargo_metadata::Metadata;
 use serde_json::{Value, from_str};
-use std::{collections::HashMap, io::Read, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Read,
+    path::PathBuf,
+};
 
 pub fn get_crates(
-    findings: &Vec<Finding>,
+    findings: &[Finding],
     packages: &[scout::output::report::Package],
     metadata: &Metadata,
 ) -> Result<HashMap<String, bool>> {
@@ -53,12 +57,12 @@ pub fn split_findings(
 //In some cases, rustc (or dylint, or clipply, or whoever) has returned the
 //package name where it should be returning the crate name. If you run into
 //problems in the future, try removing the call to this function.
-fn normalize_crate_name(s: &str) -> String {
+pub fn normalize_crate_name(s: &str) -> String {
     s.replace("-", "_")
 }
 
 fn get_crates_from_output(
-    output: &Vec<Finding>,
+    output: &[Finding],
     packages: &[scout::output::report::Package],
     metadata: &Metadata,
 ) -> Result<HashMap<String, bool>> {
@@ -125,9 +129,114 @@ pub fn temp_file_to_string(path: &PathBuf) -> Result<String> {
     Ok(ret)
 }
 
-pub fn output_to_json(output: &str) -> Vec<Value> {
+// Removes from cargo's line-delimited JSON output the detector findings that
+// are not in `kept`, leaving every other line untouched.
+pub fn filter_output(output: &str, kept: &[Finding], detectors_info: &LintStore) -> String {
+    let kept = kept
+        .iter()
+        .map(|finding| finding.json().to_string())
+        .collect::<HashSet<_>>();
     output
         .lines()
-        .map(|line| from_str::<Value>(line).unwrap())
-        .collect::<Vec<Value>>()
+        .filter(|line| {
+            let Ok(value) = from_str::<Value>(line) else {
+                return true;
+            };
+            let is_finding = Finding::parse(value.clone()).is_ok_and(|finding| {
+                finding.reason() == "compiler-message"
+                    && detectors_info.find_by_id(&finding.code()).is_some()
+            });
+            !is_finding || kept.contains(&value.to_string())
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Parses cargo's line-delimited JSON output. Blank lines are skipped.
+pub fn output_to_findings(output: &str) -> Result<Vec<Finding>> {
+    output
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            let value = from_str::<Value>(line).with_context(|| {
+                format!(
+                    "Line {} of the cargo output is not valid JSON: {}",
+                    index + 1,
+                    truncate(line, 120)
+                )
+            })?;
+            Finding::parse(value).with_context(|| {
+                format!(
+                    "Line {} of the cargo output is not a valid message: {}",
+                    index + 1,
+                    truncate(line, 120)
+                )
+            })
+        })
+        .collect()
+}
+
+fn truncate(line: &str, max_chars: usize) -> String {
+    match line.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{filter_output, output_to_findings};
+    use crate::util::detectors_info::{LintInfo, LintStore};
+
+    #[test]
+    fn parses_cargo_output() {
+        let output = concat!(
+            r#"{"reason":"compiler-message","message":{"code":{"code":"unsafe_unwrap"},"level":"warning"}}"#,
+            "\n\n",
+            r#"{"reason":"build-finished","success":true}"#,
+        );
+        let findings = output_to_findings(output).unwrap();
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].code(), "unsafe_unwrap");
+        assert!(findings[1].diagnostic().is_none());
+
+        let error = output_to_findings("{}\nerror: could not compile").unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+
+        let error = output_to_findings(concat!(
+            r#"{"reason":"build-finished","success":true}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"spans":[{"line_start":"7"}]}}"#,
+        ))
+        .unwrap_err();
+        assert!(error.to_string().contains("Line 2"));
+        assert!(format!("{error:#}").contains("not a valid diagnostic"));
+    }
+
+    #[test]
+    fn filters_findings_left_out_of_the_analysis() {
+        let output = [
+            r#"{"reason":"compiler-artifact","package_id":"token"}"#,
+            r#"{"reason":"compiler-message","message":{"code":{"code":"unsafe_unwrap"},"message":"kept"}}"#,
+            r#"{"reason":"compiler-message","message":{"code":{"code":"unsafe_unwrap"},"message":"hidden"}}"#,
+            r#"{"reason":"compiler-message","message":{"code":{"code":"dead_code"},"message":"rustc"}}"#,
+        ]
+        .join("\n");
+        let findings = output_to_findings(&output).unwrap();
+        let mut detectors_info = LintStore::new();
+        detectors_info.insert(LintInfo {
+            id: "unsafe_unwrap".to_string(),
+            ..Default::default()
+        });
+
+        let filtered = filter_output(&output, &findings[1..2], &detectors_info);
+
+        let messages = output_to_findings(&filtered)
+            .unwrap()
+            .iter()
+            .map(|finding| finding.message())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, ["", "kept", "rustc"]);
+    }
 }
@@ -0,0 +1,210 @@
+use super::suppression::lexer::{TokenKind, tokenize};
+use crate::scout::{finding::Finding, output::raw_report::extract_code_snippet};
+use sha2::{Digest, Sha256};
+use std::{fs, path::Path};
+
+// Collapses every whitespace run into a single space, so reformatting the
+// flagged code does not change its fingerprint.
+pub fn normalize_snippet(snippet: &str) -> String {
+    snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// Fingerprints a finding from values that survive unrelated edits to the file.
+// Line and column numbers are deliberately left out.
+pub fn fingerprint(
+    detector: &str,
+    package: &str,
+    file_path: &str,
+    item_path: &str,
+    snippet: &str,
+) -> String {
+    let mut hash = Sha256::new();
+    for part in [
+        detector,
+        package,
+        &file_path.replace('\\', "/"),
+        item_path,
+        &normalize_snippet(snippet),
+    ] {
+        hash.update(part.as_bytes());
+        hash.update([0]);
+    }
+    format!("{:x}", hash.finalize())
+}
+
+// Fingerprints a cargo message, given the code it flags.
+pub fn finding_fingerprint(finding: &Finding, workspace_root: &Path, snippet: &str) -> String {
+    fingerprint(
+        &finding.code(),
+        &finding.package(),
+        &finding.file_name().unwrap_or_default(),
+        &finding_item_path(finding, workspace_root),
+        snippet,
+    )
+}
+
+// Reads the code flagged by `finding`, returning an empty string if the
+// file is no longer available.
+pub fn finding_snippet(finding: &Finding, workspace_root: &Path) -> String {
+    finding
+        .file_name()
+        .and_then(|file_name| extract_code_snippet(&workspace_root.join(file_name), finding).ok())
+        .unwrap_or_default()
+}
+
+// Like `finding_snippet`, for the items that enclose the flagged code.
+pub fn finding_item_path(finding: &Finding, workspace_root: &Path) -> String {
+    let offset = finding.span().map(|span| span.byte_start);
+    match (finding.file_name(), offset) {
+        (Some(file_name), Some(offset)) => fs::read_to_string(workspace_root.join(file_name))
+            .map(|source| item_path(&source, offset as usize))
+            .unwrap_or_default(),
+        _ => String::new(),
+    }
+}
+
+// Names the modules, impls, traits and functions that enclose `offset`,
+// outermost first, e.g. `vault::Vault::withdraw`. This is a lexical scan
+// rather than a parse: it follows the braces between tokens.
+pub fn item_path(source: &str, offset: usize) -> String {
+    let tokens = tokenize(source);
+    // One entry per open brace, named if the brace opens an item
+    let mut scopes: Vec<Option<String>> = Vec::new();
+    let mut pending: Option<String> = None;
+    let mut impl_start: Option<usize> = None;
+
+    for (index, token) in tokens.iter().enumerate() {
+        if token.start >= offset {
+            break;
+        }
+        match &token.kind {
+            TokenKind::Open('{') => {
+                if let Some(start) = impl_start.take() {
+                    pending = Some(impl_name(&source[start..token.start]));
+                }
+                scopes.push(pending.take());
+            }
+            TokenKind::Close('}') => {
+                scopes.pop();
+                pending = None;
+                impl_start = None;
+            }
+            TokenKind::Punct(';') => {
+                pending = None;
+                impl_start = None;
+            }
+            TokenKind::Ident(ident) => match ident.as_str() {
+                "fn" | "mod" | "trait" if impl_start.is_none() => {
+                    if let Some(TokenKind::Ident(name)) = tokens.get(index + 1).map(|x| &x.kind) {
+                        pending = Some(name.clone());
+                    }
+                }
+                // `impl` in a function signature is a return type
+                "impl" if pending.is_none() && impl_start.is_none() => impl_start = Some(token.end),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    scopes.into_iter().flatten().collect::<Vec<_>>().join("::")
+}
+
+// `impl<T> Trait for Type<T> where ...` is named `<Type as Trait>`, and an
+// inherent impl just `Type`.
+fn impl_name(header: &str) -> String {
+    let mut header = header.trim();
+    if header.starts_with('<') {
+        header = skip_generics(header);
+    }
+    let header = header.split(" where ").next().unwrap_or_default();
+    let type_name = |s: &str| {
+        let s = s.trim().trim_start_matches('&').trim_start_matches("dyn ");
+        let s = s.split('<').next().unwrap_or_default().trim();
+        s.rsplit("::").next().unwrap_or_default().to_string()
+    };
+    match header.split_once(" for ") {
+        Some((trait_name, self_type)) => {
+            format!("<{} as {}>", type_name(self_type), type_name(trait_name))
+        }
+        None => type_name(header),
+    }
+}
+
+fn skip_generics(s: &str) -> &str {
+    let mut depth = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return &s[i + 1..];
+                }
+            }
+            _ => {}
+        }
+    }
+    ""
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fingerprint, item_path};
+
+    #[test]
+    fn ignores_whitespace_changes() {
+        let a = fingerprint(
+            "unsafe_unwrap",
+            "token",
+            "src/lib.rs",
+            "Token::transfer",
+            "foo(a, b).unwrap()",
+        );
+        let b = fingerprint(
+            "unsafe_unwrap",
+            "token",
+            "src/lib.rs",
+            "Token::transfer",
+            "foo(a,\n    b).unwrap()",
+        );
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn distinguishes_detectors_files_and_items() {
+        let base = fingerprint("unsafe_unwrap", "token", "src/lib.rs", "a", "x.unwrap()");
+        let other_detector = fingerprint("unsafe_expect", "token", "src/lib.rs", "a", "x.unwrap()");
+        let other_file = fingerprint("unsafe_unwrap", "token", "src/other.rs", "a", "x.unwrap()");
+        let other_item = fingerprint("unsafe_unwrap", "token", "src/lib.rs", "b", "x.unwrap()");
+        assert_ne!(base, other_detector);
+        assert_ne!(base, other_file);
+        assert_ne!(base, other_item);
+    }
+
+    #[test]
+    fn finds_the_enclosing_items() {
+        let source = r##"
+mod vault {
+    // fn commented() {
+    impl<'a, T: Clone> Storage<'a> for Vault<T> where T: Copy {
+        fn withdraw(&self) -> impl Iterator<Item = u8> {
+            let brace = '{';
+            let text = r#"}"#;
+            MARK
+        }
+    }
+    impl Vault {
+        fn deposit() {}
+    }
+}
+"##;
+        let offset = source.find("MARK").unwrap();
+        assert_eq!(
+            item_path(source, offset),
+            "vault::<Vault as Storage>::withdraw"
+        );
+        let offset = source.find("{}").unwrap() + 1;
+        assert_eq!(item_path(source, offset), "vault::Vault::deposit");
+    }
+}
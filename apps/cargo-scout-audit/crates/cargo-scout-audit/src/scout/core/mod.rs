@@ -1,7 +1,14 @@
+pub mod baseline;
 pub mod blockchain;
+pub mod cache;
+pub mod diff_filter;
 pub mod findings;
+pub mod fingerprint;
 pub mod nightly_runner;
+pub mod path_filter;
 pub mod post_processing;
 pub mod project_info;
+pub mod suppression;
 pub mod telemetry;
+pub mod triage;
 pub mod version_checker;
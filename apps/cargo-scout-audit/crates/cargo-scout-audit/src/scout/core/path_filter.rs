@@ -0,0 +1,140 @@
+use crate::scout::finding::Finding;
+use anyhow::{Context, Result};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PathFilterConfig {
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+    // Keyed by detector name (`unsafe-unwrap`).
+    #[serde(default)]
+    pub detector_exclude_paths: HashMap<String, Vec<String>>,
+}
+
+impl PathFilterConfig {
+    pub fn extend(&mut self, include_paths: &[String], exclude_paths: &[String]) {
+        self.include_paths.extend(include_paths.iter().cloned());
+        self.exclude_paths.extend(exclude_paths.iter().cloned());
+    }
+}
+
+// Decides which findings are in scope based on the file of their primary span.
+// Paths are matched relative to the workspace root, and `*` does not cross
+// directory boundaries (use `**` for that).
+pub struct PathFilter {
+    include: Option<GlobSet>,
+    exclude: GlobSet,
+    by_detector: HashMap<String, GlobSet>,
+}
+
+impl PathFilter {
+    pub fn new(config: &PathFilterConfig) -> Result<Self> {
+        let include = if config.include_paths.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&config.include_paths)?)
+        };
+
+        let by_detector = config
+            .detector_exclude_paths
+            .iter()
+            .map(|(detector, patterns)| Ok((detector.replace('-', "_"), build_glob_set(patterns)?)))
+            .collect::<Result<_>>()?;
+
+        Ok(PathFilter {
+            include,
+            exclude: build_glob_set(&config.exclude_paths)?,
+            by_detector,
+        })
+    }
+
+    pub fn is_excluded(&self, detector: &str, path: &str) -> bool {
+        let path = path.replace('\\', "/");
+        if let Some(include) = &self.include
+            && !include.is_match(&path)
+        {
+            return true;
+        }
+        self.exclude.is_match(&path)
+            || self
+                .by_detector
+                .get(detector)
+                .is_some_and(|globs| globs.is_match(&path))
+    }
+
+    // Returns the findings in scope and how many were excluded, per detector.
+    pub fn apply(&self, findings: Vec<Finding>) -> (Vec<Finding>, HashMap<String, u32>) {
+        let mut excluded = HashMap::new();
+        let findings = findings
+            .into_iter()
+            .filter(|finding| {
+                let Some(path) = finding.file_name() else {
+                    return true;
+                };
+                let code = finding.code();
+                if self.is_excluded(&code, &path) {
+                    *excluded.entry(code).or_insert(0) += 1;
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
+        (findings, excluded)
+    }
+}
+
+fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("Invalid path pattern '{}'", pattern))?;
+        builder.add(glob);
+    }
+    builder
+        .build()
+        .with_context(|| "Failed to build path patterns")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PathFilter, PathFilterConfig};
+    use std::collections::HashMap;
+
+    #[test]
+    fn excludes_paths_globally_and_per_detector() {
+        let filter = PathFilter::new(&PathFilterConfig {
+            include_paths: vec!["contracts/**".to_string()],
+            exclude_paths: vec!["**/generated/**".to_string()],
+            detector_exclude_paths: HashMap::from([(
+                "unsafe-unwrap".to_string(),
+                vec!["**/test*.rs".to_string()],
+            )]),
+        })
+        .unwrap();
+
+        assert!(!filter.is_excluded("unsafe_unwrap", "contracts/token/src/lib.rs"));
+        assert!(filter.is_excluded("unsafe_unwrap", "vendor/dep/src/lib.rs"));
+        assert!(filter.is_excluded("unsafe_unwrap", "contracts/token/generated/abi.rs"));
+        assert!(filter.is_excluded("unsafe_unwrap", "contracts/token/src/test.rs"));
+        assert!(!filter.is_excluded("unsafe_expect", "contracts/token/src/test.rs"));
+    }
+
+    #[test]
+    fn single_star_does_not_cross_directories() {
+        let filter = PathFilter::new(&PathFilterConfig {
+            exclude_paths: vec!["tests/*.rs".to_string()],
+            ..PathFilterConfig::default()
+        })
+        .unwrap();
+
+        assert!(filter.is_excluded("unsafe_unwrap", "tests/main.rs"));
+        assert!(!filter.is_excluded("unsafe_unwrap", "tests/common/mod.rs"));
+    }
+}
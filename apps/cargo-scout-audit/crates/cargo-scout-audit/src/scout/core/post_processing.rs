@@ -14,36 +14,29 @@ struct PostProcFinding {
 }
 
 struct FileFindings {
-    unnecessary_allows: Vec<PostProcFinding>,
     other_findings: Vec<PostProcFinding>,
 }
 
 fn parse_finding(finding: &Finding) -> Option<PostProcFinding> {
     let detector = finding.code();
-    let spans = finding.spans()?;
-    let span = spans.get(0)?;
-    let file_name = span.get("file_name")?.as_str()?;
-    let line_start = span.get("line_start")?.as_u64()?;
-    let line_end = span.get("line_end")?.as_u64()?;
+    let span = finding.span()?;
 
     let allowed_lint = if detector == "unnecessary_lint_allow" {
         finding
-            .children()?
-            .get(0)?
-            .get("message")?
-            .as_str()
-            .and_then(|msg| msg.split('`').nth(1).map(String::from))
+            .children()
+            .first()?
+            .message
+            .split('`')
+            .nth(1)
+            .map(String::from)
     } else {
         None
     };
 
-    let start = usize::try_from(line_start).ok()?;
-    let end = usize::try_from(line_end).ok()?;
-
     Some(PostProcFinding {
-        detector: detector.to_owned(),
-        file_name: file_name.to_owned(),
-        span: (start, end),
+        detector,
+        file_name: span.file_name.clone(),
+        span: (span.line_start, span.line_end),
         allowed_lint,
     })
 }
@@ -57,7 +50,6 @@ impl FindingsCache {
                 by_file
                     .entry(parsed.file_name.clone())
                     .or_insert_with(|| FileFindings {
-                        unnecessary_allows: Vec::new(),
                         other_findings: Vec::new(),
                     })
                     .add_finding(parsed);
@@ -70,9 +62,7 @@ impl FindingsCache {
 
 impl FileFindings {
     fn add_finding(&mut self, finding: PostProcFinding) {
-        if finding.detector == "unnecessary_lint_allow" {
-            self.unnecessary_allows.push(finding);
-        } else {
+        if finding.detector != "unnecessary_lint_allow" {
             self.other_findings.push(finding);
         }
     }
@@ -88,20 +78,20 @@ fn should_include_finding(finding: &Finding, cache: &FindingsCache) -> bool {
         None => return false, // If we can't parse the finding, we don't include it
     };
 
+    // The findings covered by a `scout_allow` are removed later by
+    // `suppression::Suppressions`, so here we only drop the allows that are needed
+    if current_finding.detector != "unnecessary_lint_allow" {
+        return true;
+    }
+
     if let Some(file_findings) = cache.by_file.get(&current_finding.file_name) {
-        if current_finding.detector == "unnecessary_lint_allow" {
-            if let Some(allowed_lint) = &current_finding.allowed_lint {
-                !file_findings.other_findings.iter().any(|f| {
-                    &f.detector == allowed_lint && spans_overlap(f.span, current_finding.span)
-                })
-            } else {
-                true // Include if we can't determine the allowed lint
-            }
+        if let Some(allowed_lint) = &current_finding.allowed_lint {
+            !file_findings
+                .other_findings
+                .iter()
+                .any(|f| &f.detector == allowed_lint && spans_overlap(f.span, current_finding.span))
         } else {
-            !file_findings.unnecessary_allows.iter().any(|allow| {
-                (allow.allowed_lint.as_ref() == Some(&current_finding.detector))
-                    && spans_overlap(allow.span, current_finding.span)
-            })
+            true // Include if we can't determine the allowed lint
         }
     } else {
         true // If we can't find the file, we include it by default
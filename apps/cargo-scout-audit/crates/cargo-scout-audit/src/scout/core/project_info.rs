@@ -3,6 +3,7 @@ use anyhow::{Context, Result, anyhow, bail};
 use cargo_metadata::{Metadata, MetadataCommand, camino::Utf8PathBuf};
 use lazy_static::lazy_static;
 use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::{fs, path::PathBuf};
 use thiserror::Error;
 
@@ -18,6 +19,54 @@ lazy_static! {
     static ref NAME_REGEX: Regex = Regex::new(r"(^|\s)\w").expect("Invalid regex");
 }
 
+// Workspace members to analyze, from `--package` and `--exclude-package` or
+// the `packages` key of the project configuration. When nothing is included,
+// cargo's default members are.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PackageScope {
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl PackageScope {
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    // Takes the included members, or `defaults`, without the excluded ones.
+    fn select<T: Copy + PartialEq>(
+        &self,
+        members: &[(&str, T)],
+        defaults: Vec<T>,
+    ) -> Result<Vec<T>> {
+        let find = |name: &String| {
+            members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, id)| *id)
+                .ok_or_else(|| anyhow!("Package '{}' is not a member of the workspace", name))
+        };
+        let mut selected = if self.include.is_empty() {
+            defaults
+        } else {
+            self.include.iter().map(find).collect::<Result<Vec<_>>>()?
+        };
+        for name in self.exclude.iter() {
+            let id = find(name)?;
+            selected.retain(|selected| *selected != id);
+        }
+        if selected.is_empty() {
+            bail!(
+                "No packages left to analyze after excluding {}",
+                self.exclude.join(", ")
+            );
+        }
+        Ok(selected)
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum MetadataError {
     #[error("Invalid manifest path. Ensure scout is being run in a Rust project. (Path: {0})")]
@@ -53,8 +102,8 @@ impl Project {
     }
 
     #[tracing::instrument(name = "GET PROJECT INFO", skip_all)]
-    pub fn get_info(metadata: &Metadata) -> Result<Self> {
-        let packages = Self::collect_packages(metadata)?;
+    pub fn get_info(metadata: &Metadata, scope: &PackageScope) -> Result<Self> {
+        let packages = Self::collect_packages(metadata, scope)?;
         let project_name = Self::format_project_name(&metadata.workspace_root)?;
         let date = chrono::Local::now().format("%Y-%m-%d").to_string();
 
@@ -68,11 +117,13 @@ impl Project {
         Ok(project_info)
     }
 
-    fn collect_packages(metadata: &Metadata) -> Result<Vec<Package>> {
+    fn collect_packages(metadata: &Metadata, scope: &PackageScope) -> Result<Vec<Package>> {
         let mut packages = Vec::new();
         let workspace_root = &metadata.workspace_root;
 
-        let package_ids = if let Some(root_package) = metadata.root_package() {
+        let default_ids = if !scope.include.is_empty() {
+            Vec::new()
+        } else if let Some(root_package) = metadata.root_package() {
             // Single package case
             vec![&root_package.id]
         } else if !metadata.workspace_default_members.is_empty() {
@@ -84,7 +135,15 @@ impl Project {
             );
         };
 
-        let is_single_package = metadata.root_package().is_some();
+        let members = metadata
+            .workspace_members
+            .iter()
+            .filter_map(|id| {
+                let package = metadata.packages.iter().find(|p| &p.id == id)?;
+                Some((package.name.as_str(), id))
+            })
+            .collect::<Vec<_>>();
+        let package_ids = scope.select(&members, default_ids)?;
 
         for package_id in package_ids {
             let package = metadata
@@ -96,7 +155,10 @@ impl Project {
             let absolute_path: PathBuf = manifest_path.clone().into();
 
             // Calculate relative path
-            let relative_path = if is_single_package {
+            let relative_path = if metadata
+                .root_package()
+                .is_some_and(|root_package| &root_package.id == package_id)
+            {
                 // Single package case
                 PathBuf::from("./Cargo.toml")
             } else {
@@ -134,3 +196,34 @@ impl Project {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PackageScope;
+
+    #[test]
+    fn selects_workspace_members() {
+        let members = [("token", 0), ("vault", 1), ("utils", 2)];
+        let scope = |include: &[&str], exclude: &[&str]| PackageScope {
+            include: include.iter().map(|x| x.to_string()).collect(),
+            exclude: exclude.iter().map(|x| x.to_string()).collect(),
+        };
+
+        assert_eq!(
+            scope(&[], &[]).select(&members, vec![0, 1]).unwrap(),
+            [0, 1]
+        );
+        assert_eq!(
+            scope(&["utils", "token"], &[])
+                .select(&members, vec![0, 1])
+                .unwrap(),
+            [2, 0]
+        );
+        assert_eq!(
+            scope(&[], &["token"]).select(&members, vec![0, 1]).unwrap(),
+            [1]
+        );
+        assert!(scope(&["oracle"], &[]).select(&members, vec![0]).is_err());
+        assert!(scope(&[], &["token"]).select(&members, vec![0]).is_err());
+    }
+}
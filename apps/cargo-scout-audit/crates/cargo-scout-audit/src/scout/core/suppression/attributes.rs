@@ -0,0 +1,408 @@
+use super::lexer::{Token, TokenKind, tokenize};
+
+// Keywords that start an item, whose scope ends with its body or its `;`.
+const ITEM_KEYWORDS: &[&str] = &[
+    "async",
+    "const",
+    "enum",
+    "extern",
+    "fn",
+    "impl",
+    "macro_rules",
+    "mod",
+    "pub",
+    "static",
+    "struct",
+    "trait",
+    "type",
+    "union",
+    "unsafe",
+    "use",
+];
+
+// A `scout_allow` attribute and the byte range of the code it applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoutAllow {
+    pub detectors: Vec<String>,
+    pub reason: Option<String>,
+    pub line: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ScoutAllow {
+    pub fn covers(&self, detector: &str, byte: usize) -> bool {
+        self.start <= byte && byte < self.end && self.detectors.iter().any(|d| d == detector)
+    }
+}
+
+// A `mod name;` whose contents live in another file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDeclaration {
+    pub name: String,
+    pub start: usize,
+}
+
+pub struct SourceAttributes {
+    pub allows: Vec<ScoutAllow>,
+    pub modules: Vec<ModuleDeclaration>,
+}
+
+pub fn parse_source(source: &str) -> SourceAttributes {
+    let tokens = tokenize(source);
+    SourceAttributes {
+        allows: scout_allows(&tokens, source),
+        modules: module_declarations(&tokens),
+    }
+}
+
+#[cfg(test)]
+pub fn find_scout_allows(source: &str) -> Vec<ScoutAllow> {
+    scout_allows(&tokenize(source), source)
+}
+
+fn scout_allows(tokens: &[Token], source: &str) -> Vec<ScoutAllow> {
+    let mut allows = Vec::new();
+
+    let mut i = 0;
+    while i < tokens.len() {
+        let Some(attribute) = parse_attribute(tokens, i) else {
+            i += 1;
+            continue;
+        };
+        if let Some((detectors, reason)) = parse_scout_allow(&tokens[attribute.content.clone()]) {
+            let (start, end) = if attribute.inner {
+                enclosing_block(tokens, i, source.len())
+            } else {
+                (
+                    tokens[i].start,
+                    outer_scope_end(tokens, attribute.end, source.len()),
+                )
+            };
+            allows.push(ScoutAllow {
+                detectors,
+                reason,
+                line: source[..tokens[i].start].matches('\n').count() + 1,
+                start,
+                end,
+            });
+        }
+        i = attribute.end;
+    }
+
+    allows
+}
+
+// Top-level `mod name;` declarations. The ones with a `#[path]` attribute are
+// left out, as their file can't be found from the name.
+fn module_declarations(tokens: &[Token]) -> Vec<ModuleDeclaration> {
+    let mut modules = Vec::new();
+    let mut depth = 0usize;
+    let mut has_path = false;
+
+    let mut i = 0;
+    while let Some(token) = tokens.get(i) {
+        if depth == 0
+            && let Some(attribute) = parse_attribute(tokens, i)
+        {
+            has_path |= tokens[attribute.content.clone()]
+                .first()
+                .is_some_and(|t| t.is_ident("path"));
+            i = attribute.end;
+            continue;
+        }
+        match &token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(_) => {
+                depth = depth.saturating_sub(1);
+                has_path = false;
+            }
+            TokenKind::Ident(ident) if depth == 0 && ident == "mod" => {
+                if let (
+                    Some(Token {
+                        kind: TokenKind::Ident(name),
+                        ..
+                    }),
+                    Some(semicolon),
+                ) = (tokens.get(i + 1), tokens.get(i + 2))
+                    && semicolon.is_punct(';')
+                    && !has_path
+                {
+                    modules.push(ModuleDeclaration {
+                        name: name.clone(),
+                        start: token.start,
+                    });
+                }
+            }
+            TokenKind::Punct(';') if depth == 0 => has_path = false,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    modules
+}
+
+struct Attribute {
+    inner: bool,
+    // Token range between the brackets
+    content: std::ops::Range<usize>,
+    // Index of the first token after the attribute
+    end: usize,
+}
+
+fn parse_attribute(tokens: &[Token], i: usize) -> Option<Attribute> {
+    if !tokens.get(i)?.is_punct('#') {
+        return None;
+    }
+    let inner = tokens.get(i + 1)?.is_punct('!');
+    let open = if inner { i + 2 } else { i + 1 };
+    if !tokens.get(open)?.is_open('[') {
+        return None;
+    }
+    let close = matching_close(tokens, open)?;
+    Some(Attribute {
+        inner,
+        content: open + 1..close,
+        end: close + 1,
+    })
+}
+
+// Returns the index of the delimiter closing the one at `open`.
+fn matching_close(tokens: &[Token], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(open) {
+        match token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(_) => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+// Accepts `scout_allow(...)`, `scout_utils::scout_allow(...)` and the same
+// wrapped in `cfg_attr(...)`. Returns the detectors and the reason, if any.
+fn parse_scout_allow(content: &[Token]) -> Option<(Vec<String>, Option<String>)> {
+    let position = if content.first()?.is_ident("cfg_attr") {
+        content
+            .windows(2)
+            .position(|pair| pair[0].is_ident("scout_allow") && pair[1].is_open('('))?
+    } else {
+        let mut position = 0;
+        while content.get(position + 1).is_some_and(|t| t.is_punct(':'))
+            && content.get(position + 2).is_some_and(|t| t.is_punct(':'))
+        {
+            position += 3;
+        }
+        position
+    };
+    if !content.get(position)?.is_ident("scout_allow") || !content.get(position + 1)?.is_open('(') {
+        return None;
+    }
+
+    let close = matching_close(content, position + 1)?;
+    let mut detectors = Vec::new();
+    let mut reason = None;
+    for argument in content[position + 2..close].split(|t| t.is_punct(',')) {
+        if let Some(detector) = detector_name(argument) {
+            detectors.push(detector);
+            continue;
+        }
+        match argument {
+            [
+                key,
+                eq,
+                Token {
+                    kind: TokenKind::Str(value),
+                    ..
+                },
+            ] if key.is_ident("reason") && eq.is_punct('=') => reason = Some(value.clone()),
+            _ => {}
+        }
+    }
+
+    Some((detectors, reason))
+}
+
+// `unsafe_unwrap`, or the dashed `unsafe-unwrap`, which lexes as identifiers
+// separated by `-`.
+fn detector_name(argument: &[Token]) -> Option<String> {
+    let mut parts = Vec::new();
+    for (index, token) in argument.iter().enumerate() {
+        match &token.kind {
+            TokenKind::Ident(part) if index % 2 == 0 => parts.push(part.as_str()),
+            TokenKind::Punct('-') if index % 2 == 1 => {}
+            _ => return None,
+        }
+    }
+    (argument.len() % 2 == 1).then(|| parts.join("_"))
+}
+
+// An inner attribute applies to the block that contains it, or to the whole
+// file at the top level.
+fn enclosing_block(tokens: &[Token], i: usize, source_len: usize) -> (usize, usize) {
+    let mut depth = 0usize;
+    for open in (0..i).rev() {
+        match tokens[open].kind {
+            TokenKind::Close(_) => depth += 1,
+            TokenKind::Open('{') if depth == 0 => {
+                let end = matching_close(tokens, open)
+                    .map(|close| tokens[close].end)
+                    .unwrap_or(source_len);
+                return (tokens[open].start, end);
+            }
+            TokenKind::Open(_) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    (0, source_len)
+}
+
+// Finds where the code annotated by an outer attribute ends: the body or `;`
+// of an item, or the end of a statement, field or match arm.
+fn outer_scope_end(tokens: &[Token], mut i: usize, source_len: usize) -> usize {
+    // Other attributes on the same item
+    while let Some(attribute) = parse_attribute(tokens, i) {
+        i = attribute.end;
+    }
+    let Some(first) = tokens.get(i) else {
+        return source_len;
+    };
+
+    let is_item =
+        matches!(&first.kind, TokenKind::Ident(ident) if ITEM_KEYWORDS.contains(&ident.as_str()));
+    let is_let = first.is_ident("let");
+
+    let mut depth = 0usize;
+    let mut index = i;
+    while let Some(token) = tokens.get(index) {
+        match &token.kind {
+            TokenKind::Open(_) => depth += 1,
+            TokenKind::Close(c) => {
+                if depth == 0 {
+                    // The enclosing block ends first
+                    return token.start;
+                }
+                depth -= 1;
+                if depth == 0 && *c == '}' {
+                    let next = tokens.get(index + 1);
+                    if is_item {
+                        return token.end;
+                    }
+                    let continues = next.is_some_and(|next| {
+                        next.is_ident("else") || next.is_punct('.') || next.is_punct('?')
+                    });
+                    if !is_let && !continues {
+                        return match next {
+                            Some(next) if next.is_punct(';') || next.is_punct(',') => next.end,
+                            _ => token.end,
+                        };
+                    }
+                }
+            }
+            TokenKind::Punct(';') if depth == 0 => return token.end,
+            TokenKind::Punct(',') if depth == 0 && !is_item && !is_let => return token.end,
+            _ => {}
+        }
+        index += 1;
+    }
+
+    source_len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ModuleDeclaration, find_scout_allows, parse_source};
+
+    fn scope(source: &str, index: usize) -> &str {
+        let allow = &find_scout_allows(source)[index];
+        &source[allow.start..allow.end]
+    }
+
+    #[test]
+    fn parses_detectors_and_reason() {
+        let allows = find_scout_allows(
+            r#"#[scout_allow(unsafe_unwrap, unsafe_expect, reason = "checked above")]
+            fn f() {}"#,
+        );
+        assert_eq!(allows[0].detectors, ["unsafe_unwrap", "unsafe_expect"]);
+        assert_eq!(allows[0].reason.as_deref(), Some("checked above"));
+        assert_eq!(allows[0].line, 1);
+    }
+
+    #[test]
+    fn accepts_dashed_detector_names() {
+        let allows = find_scout_allows(
+            "#[cfg_attr(scout, scout_allow(unsafe-unwrap, integer-overflow-or-underflow))] fn f() {}",
+        );
+        assert_eq!(
+            allows[0].detectors,
+            ["unsafe_unwrap", "integer_overflow_or_underflow"]
+        );
+    }
+
+    #[test]
+    fn outer_attribute_scopes() {
+        let source = r#"
+            #[scout_allow(unsafe_unwrap)]
+            pub fn f() -> Result<(), ()> { a.unwrap(); }
+            fn g() {
+                #[scout_allow(unsafe_unwrap)]
+                let x = if c { a.unwrap() } else { b };
+                #[scout_utils::scout_allow(unsafe_unwrap)]
+                b.unwrap();
+                c.unwrap();
+            }
+        "#;
+        assert_eq!(
+            scope(source, 0),
+            "#[scout_allow(unsafe_unwrap)]\n            pub fn f() -> Result<(), ()> { a.unwrap(); }"
+        );
+        assert!(scope(source, 1).ends_with("else { b };"));
+        assert!(scope(source, 2).ends_with("b.unwrap();"));
+    }
+
+    #[test]
+    fn inner_and_cfg_attr_attributes() {
+        let source = r#"
+            mod m {
+                #![cfg_attr(test, scout_allow(unsafe_unwrap, reason = "tests"))]
+                fn f() { a.unwrap(); }
+            }
+            fn g() {}
+        "#;
+        let allows = find_scout_allows(source);
+        assert_eq!(allows[0].reason.as_deref(), Some("tests"));
+        assert!(scope(source, 0).starts_with("{"));
+        assert!(
+            scope(source, 0)
+                .trim_end()
+                .ends_with("a.unwrap(); }\n            }")
+        );
+    }
+
+    #[test]
+    fn out_of_line_module_declarations() {
+        let source = r#"
+            #[scout_allow(unsafe_unwrap)]
+            pub mod generated;
+            #[path = "other.rs"]
+            mod renamed;
+            mod inline { mod nested; }
+            mod plain;
+        "#;
+        let names = parse_source(source)
+            .modules
+            .into_iter()
+            .map(|ModuleDeclaration { name, .. }| name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["generated", "plain"]);
+        assert!(scope(source, 0).ends_with("pub mod generated;"));
+    }
+}
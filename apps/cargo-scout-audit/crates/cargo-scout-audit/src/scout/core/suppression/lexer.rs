@@ -0,0 +1,284 @@
+// A minimal Rust lexer, just precise enough to find attributes and the
+// extent of the code they apply to. Comments are skipped, and the contents of
+// strings and char literals never leak out as tokens.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    Ident(String),
+    Str(String),
+    // Numbers, chars, byte strings and lifetimes
+    Literal,
+    Punct(char),
+    Open(char),
+    Close(char),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn is_ident(&self, name: &str) -> bool {
+        matches!(&self.kind, TokenKind::Ident(ident) if ident == name)
+    }
+
+    pub fn is_punct(&self, c: char) -> bool {
+        self.kind == TokenKind::Punct(c)
+    }
+
+    pub fn is_open(&self, c: char) -> bool {
+        self.kind == TokenKind::Open(c)
+    }
+}
+
+struct Lexer<'a> {
+    bytes: &'a [u8],
+    source: &'a str,
+    pos: usize,
+}
+
+pub fn tokenize(source: &str) -> Vec<Token> {
+    let mut lexer = Lexer {
+        bytes: source.as_bytes(),
+        source,
+        pos: 0,
+    };
+    let mut tokens = Vec::new();
+    while let Some(token) = lexer.next_token() {
+        tokens.push(token);
+    }
+    tokens
+}
+
+fn is_ident_start(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphabetic() || c >= 0x80
+}
+
+fn is_ident_continue(c: u8) -> bool {
+    c == b'_' || c.is_ascii_alphanumeric() || c >= 0x80
+}
+
+impl Lexer<'_> {
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.pos + offset).copied()
+    }
+
+    fn next_token(&mut self) -> Option<Token> {
+        self.skip_trivia();
+        let start = self.pos;
+        let c = self.peek(0)?;
+
+        let kind = match c {
+            b'"' => TokenKind::Str(self.string()),
+            b'r' if matches!(self.peek(1), Some(b'"') | Some(b'#'))
+                && self.raw_string_hashes(1).is_some() =>
+            {
+                self.pos += 1;
+                TokenKind::Str(self.raw_string())
+            }
+            b'b' | b'c' if self.peek(1) == Some(b'"') => {
+                self.pos += 1;
+                self.string();
+                TokenKind::Literal
+            }
+            b'b' | b'c' if self.peek(1) == Some(b'r') && self.raw_string_hashes(2).is_some() => {
+                self.pos += 2;
+                self.raw_string();
+                TokenKind::Literal
+            }
+            b'b' if self.peek(1) == Some(b'\'') => {
+                self.pos += 1;
+                self.quote();
+                TokenKind::Literal
+            }
+            b'\'' => {
+                self.quote();
+                TokenKind::Literal
+            }
+            b'0'..=b'9' => {
+                while self.peek(0).is_some_and(is_ident_continue) {
+                    self.pos += 1;
+                }
+                TokenKind::Literal
+            }
+            c if is_ident_start(c) => {
+                // Raw identifiers (`r#type`) are plain identifiers here
+                if c == b'r'
+                    && self.peek(1) == Some(b'#')
+                    && self.peek(2).is_some_and(is_ident_start)
+                {
+                    self.pos += 2;
+                }
+                let ident_start = self.pos;
+                while self.peek(0).is_some_and(is_ident_continue) {
+                    self.pos += 1;
+                }
+                TokenKind::Ident(self.source[ident_start..self.pos].to_string())
+            }
+            b'(' | b'[' | b'{' => {
+                self.pos += 1;
+                TokenKind::Open(c as char)
+            }
+            b')' | b']' | b'}' => {
+                self.pos += 1;
+                TokenKind::Close(c as char)
+            }
+            _ => {
+                // Keep multi-byte characters in one piece
+                let c = self.source[self.pos..].chars().next()?;
+                self.pos += c.len_utf8();
+                TokenKind::Punct(c)
+            }
+        };
+
+        Some(Token {
+            kind,
+            start,
+            end: self.pos,
+        })
+    }
+
+    fn skip_trivia(&mut self) {
+        loop {
+            match (self.peek(0), self.peek(1)) {
+                (Some(c), _) if c.is_ascii_whitespace() => self.pos += 1,
+                (Some(b'/'), Some(b'/')) => {
+                    while self.peek(0).is_some_and(|c| c != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                (Some(b'/'), Some(b'*')) => {
+                    self.pos += 2;
+                    let mut depth = 1;
+                    while depth > 0 {
+                        match (self.peek(0), self.peek(1)) {
+                            (None, _) => return,
+                            (Some(b'/'), Some(b'*')) => {
+                                depth += 1;
+                                self.pos += 2;
+                            }
+                            (Some(b'*'), Some(b'/')) => {
+                                depth -= 1;
+                                self.pos += 2;
+                            }
+                            _ => self.pos += 1,
+                        }
+                    }
+                }
+                _ => return,
+            }
+        }
+    }
+
+    // Consumes a string starting at the opening quote and returns its contents.
+    // Escapes are kept as written, which is enough for a justification message.
+    fn string(&mut self) -> String {
+        self.pos += 1;
+        let start = self.pos;
+        while let Some(c) = self.peek(0) {
+            match c {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    let contents = self.source[start..self.pos].to_string();
+                    self.pos += 1;
+                    return contents;
+                }
+                _ => self.pos += 1,
+            }
+        }
+        self.pos = self.bytes.len();
+        self.source[start..].to_string()
+    }
+
+    // Returns the number of `#` of a raw string whose `r` is at `offset`.
+    fn raw_string_hashes(&self, offset: usize) -> Option<usize> {
+        let mut hashes = 0;
+        while self.peek(offset + hashes) == Some(b'#') {
+            hashes += 1;
+        }
+        (self.peek(offset + hashes) == Some(b'"')).then_some(hashes)
+    }
+
+    // Consumes a raw string starting at its first `#` or quote.
+    fn raw_string(&mut self) -> String {
+        let hashes = self.raw_string_hashes(0).unwrap_or_default();
+        self.pos += hashes + 1;
+        let start = self.pos;
+        let terminator = format!("\"{}", "#".repeat(hashes));
+        match self.source[start..].find(&terminator) {
+            Some(length) => {
+                self.pos = start + length + terminator.len();
+                self.source[start..start + length].to_string()
+            }
+            None => {
+                self.pos = self.bytes.len();
+                self.source[start..].to_string()
+            }
+        }
+    }
+
+    // Consumes either a char literal or a lifetime, starting at the quote.
+    fn quote(&mut self) {
+        self.pos += 1;
+        if self.peek(0) == Some(b'\\') {
+            self.pos += 2;
+            while self.peek(0).is_some_and(|c| c != b'\'' && c != b'\n') {
+                self.pos += 1;
+            }
+            if self.peek(0) == Some(b'\'') {
+                self.pos += 1;
+            }
+            return;
+        }
+        let Some(c) = self.source[self.pos..].chars().next() else {
+            return;
+        };
+        if self.bytes.get(self.pos + c.len_utf8()) == Some(&b'\'') {
+            // 'x'
+            self.pos += c.len_utf8() + 1;
+        } else {
+            // 'lifetime
+            while self.peek(0).is_some_and(is_ident_continue) {
+                self.pos += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TokenKind, tokenize};
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        tokenize(source)
+            .into_iter()
+            .map(|token| token.kind)
+            .collect()
+    }
+
+    #[test]
+    fn skips_comments_and_string_contents() {
+        let source = r##"/* #[a] /* nested */ */ x // #[b]
+            "#[c]" r#"#[d]"# '}' 'a"##;
+        assert_eq!(
+            kinds(source),
+            [
+                TokenKind::Ident("x".to_string()),
+                TokenKind::Str("#[c]".to_string()),
+                TokenKind::Str("#[d]".to_string()),
+                TokenKind::Literal,
+                TokenKind::Literal,
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_byte_offsets() {
+        let tokens = tokenize("fn é() {}");
+        assert_eq!((tokens[1].start, tokens[1].end), (3, 5));
+        assert_eq!(tokens[2].kind, TokenKind::Open('('));
+    }
+}
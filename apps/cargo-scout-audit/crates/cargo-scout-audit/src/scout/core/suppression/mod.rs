@@ -0,0 +1,252 @@
+mod attributes;
+pub(crate) mod lexer;
+
+use crate::scout::finding::Finding;
+use attributes::{SourceAttributes, parse_source};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+// Bounds the walk up the module tree, in case of cyclic declarations.
+const MAX_MODULE_DEPTH: usize = 32;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SuppressedFinding {
+    pub vulnerability_id: String,
+    pub package: String,
+    pub file_path: String,
+    pub line: u64,
+    pub error_message: String,
+    pub reason: String,
+}
+
+// A `scout_allow` that was ignored because it has no reason and the project
+// requires one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnjustifiedAllow {
+    pub file_path: String,
+    pub line: usize,
+}
+
+// Applies the `#[scout_allow(...)]` attributes found in the analyzed sources,
+// independently of the detectors that were loaded.
+pub struct Suppressions {
+    workspace_root: PathBuf,
+    require_justification: bool,
+    by_file: HashMap<String, SourceAttributes>,
+    unjustified: HashSet<UnjustifiedAllow>,
+}
+
+impl Suppressions {
+    pub fn new(workspace_root: &Path, require_justification: bool) -> Self {
+        Suppressions {
+            workspace_root: workspace_root.to_path_buf(),
+            require_justification,
+            by_file: HashMap::new(),
+            unjustified: HashSet::new(),
+        }
+    }
+
+    // Returns the findings that are not suppressed, and the suppressed ones.
+    pub fn apply(&mut self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<SuppressedFinding>) {
+        let mut kept = Vec::new();
+        let mut suppressed = Vec::new();
+        for finding in findings {
+            match self.find_reason(&finding) {
+                Some(reason) => suppressed.push(SuppressedFinding {
+                    vulnerability_id: finding.code(),
+                    package: finding.package(),
+                    file_path: finding.file_name().unwrap_or_default(),
+                    line: finding.span().map_or(0, |span| span.line_start as u64),
+                    error_message: finding.message(),
+                    reason,
+                }),
+                None => kept.push(finding),
+            }
+        }
+        (kept, suppressed)
+    }
+
+    pub fn unjustified(&self) -> Vec<&UnjustifiedAllow> {
+        let mut unjustified = self.unjustified.iter().collect::<Vec<_>>();
+        unjustified.sort_by(|a, b| (&a.file_path, a.line).cmp(&(&b.file_path, b.line)));
+        unjustified
+    }
+
+    fn find_reason(&mut self, finding: &Finding) -> Option<String> {
+        if finding.reason() != "compiler-message" {
+            return None;
+        }
+        let file_path = finding.file_name()?;
+        let byte = finding.span()?.byte_start as usize;
+        self.find_reason_at(file_path, &finding.code(), byte, MAX_MODULE_DEPTH)
+    }
+
+    // Looks for an allow covering `byte` of the file, and then for one
+    // covering the `mod` declaration of the file in its parent module, as
+    // rustc does with lint attributes.
+    fn find_reason_at(
+        &mut self,
+        file_path: String,
+        detector: &str,
+        byte: usize,
+        depth: usize,
+    ) -> Option<String> {
+        let covering = self
+            .attributes(&file_path)
+            .allows
+            .iter()
+            .filter(|allow| allow.covers(detector, byte))
+            .cloned()
+            .collect::<Vec<_>>();
+        for allow in covering {
+            match allow.reason {
+                Some(reason) => return Some(reason),
+                None if !self.require_justification => return Some(String::new()),
+                None => {
+                    self.unjustified.insert(UnjustifiedAllow {
+                        file_path: file_path.clone(),
+                        line: allow.line,
+                    });
+                }
+            }
+        }
+
+        let (parent, declaration) = self.parent_module(&file_path)?;
+        self.find_reason_at(parent, detector, declaration, depth.checked_sub(1)?)
+    }
+
+    fn attributes(&mut self, file_path: &str) -> &SourceAttributes {
+        let workspace_root = &self.workspace_root;
+        self.by_file
+            .entry(file_path.to_string())
+            .or_insert_with(|| {
+                let source =
+                    std::fs::read_to_string(workspace_root.join(file_path)).unwrap_or_default();
+                parse_source(&source)
+            })
+    }
+
+    // The file declaring the module of `file_path` with `mod name;`, and the
+    // offset of that declaration. `a/b.rs` and `a/b/mod.rs` may be declared
+    // in `a/mod.rs`, `a/lib.rs`, `a/main.rs` or `a.rs`.
+    fn parent_module(&mut self, file_path: &str) -> Option<(String, usize)> {
+        let path = Path::new(file_path);
+        let stem = path.file_stem()?.to_str()?;
+        let (name, directory) = if stem == "mod" {
+            let directory = path.parent()?;
+            (directory.file_name()?.to_str()?, directory.parent()?)
+        } else {
+            (stem, path.parent()?)
+        };
+
+        let mut candidates = ["mod.rs", "lib.rs", "main.rs"]
+            .map(|file| directory.join(file))
+            .to_vec();
+        if let (Some(parent), Some(directory_name)) = (directory.parent(), directory.file_name()) {
+            candidates.push(parent.join(format!("{}.rs", directory_name.to_string_lossy())));
+        }
+
+        candidates
+            .into_iter()
+            .filter(|candidate| candidate.as_path() != path)
+            .find_map(|candidate| {
+                let candidate = candidate.to_string_lossy().into_owned();
+                let declaration = self
+                    .attributes(&candidate)
+                    .modules
+                    .iter()
+                    .find(|module| module.name == name)?
+                    .start;
+                Some((candidate, declaration))
+            })
+    }
+}
+
+// Number of suppressed findings of each detector, by dashed detector name.
+pub fn count_by_detector(suppressed: &[SuppressedFinding]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for finding in suppressed {
+        *counts
+            .entry(finding.vulnerability_id.replace('_', "-"))
+            .or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SuppressedFinding, Suppressions, count_by_detector};
+    use crate::scout::finding::Finding;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn counts_suppressed_findings_by_detector() {
+        let suppressed = ["unsafe_unwrap", "overflow_check", "unsafe_unwrap"]
+            .into_iter()
+            .map(|detector| SuppressedFinding {
+                vulnerability_id: detector.to_string(),
+                package: "token".to_string(),
+                file_path: "src/lib.rs".to_string(),
+                line: 1,
+                error_message: String::new(),
+                reason: String::new(),
+            })
+            .collect::<Vec<_>>();
+        let counts = count_by_detector(&suppressed)
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            counts,
+            [
+                ("overflow-check".to_string(), 1),
+                ("unsafe-unwrap".to_string(), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn outer_attributes_on_module_declarations_cover_the_module_file() {
+        let directory = tempfile::tempdir().unwrap();
+        let src = directory.path().join("src");
+        fs::create_dir_all(src.join("generated")).unwrap();
+        fs::write(
+            src.join("lib.rs"),
+            "#[scout_allow(unsafe_unwrap, reason = \"generated\")]\nmod generated;\nmod vault;\n",
+        )
+        .unwrap();
+        fs::write(src.join("generated").join("mod.rs"), "mod bindings;\n").unwrap();
+        fs::write(src.join("generated").join("bindings.rs"), "fn f() {}\n").unwrap();
+        fs::write(src.join("vault.rs"), "fn f() {}\n").unwrap();
+
+        let finding = |file_name: &str| {
+            Finding::new(json!({
+                "reason": "compiler-message",
+                "message": {
+                    "code": { "code": "unsafe_unwrap" },
+                    "spans": [{ "file_name": file_name, "byte_start": 3 }],
+                },
+            }))
+        };
+        let mut suppressions = Suppressions::new(directory.path(), false);
+        let (kept, suppressed) = suppressions.apply(vec![
+            finding("src/generated/mod.rs"),
+            finding("src/generated/bindings.rs"),
+            finding("src/vault.rs"),
+        ]);
+        assert_eq!(
+            suppressed
+                .iter()
+                .map(|finding| (finding.file_path.as_str(), finding.reason.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                ("src/generated/mod.rs", "generated"),
+                ("src/generated/bindings.rs", "generated")
+            ]
+        );
+        assert_eq!(kept[0].file_name().as_deref(), Some("src/vault.rs"));
+    }
+}
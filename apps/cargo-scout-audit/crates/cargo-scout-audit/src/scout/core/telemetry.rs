@@ -1,15 +1,73 @@
 use super::blockchain::BlockChain;
-use crate::{cli_args::Scout, util::home::get_config_directory};
+use crate::{
+    cli_args::Scout,
+    util::{env::SCOUT_NO_TELEMETRY, home::get_config_directory},
+};
 use anyhow::{Context, Result};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use std::{
-    env, fs,
+    env, fmt, fs,
+    path::PathBuf,
     time::{SystemTime, UNIX_EPOCH},
 };
 use strum::EnumIter;
 
 const SCOUT_TELEMETRY_URL: &str = "https://scout-api.coinfabrik.com";
+const DO_NOT_TRACK: &str = "DONOTTRACK";
+
+// Whether Scout may contact its servers, for telemetry and update checks,
+// and what turned it off. Checked in order of precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryStatus {
+    Enabled,
+    DisabledByFlag,
+    DisabledByEnvironment,
+    DisabledByConfig,
+    DisabledByUser,
+}
+
+impl TelemetryStatus {
+    pub fn get(no_telemetry_flag: bool, enabled_by_config: bool) -> Self {
+        if no_telemetry_flag {
+            TelemetryStatus::DisabledByFlag
+        } else if env::var(SCOUT_NO_TELEMETRY).is_ok_and(|value| is_truthy(&value)) {
+            TelemetryStatus::DisabledByEnvironment
+        } else if !enabled_by_config {
+            TelemetryStatus::DisabledByConfig
+        } else if TelemetryClient::stored_user_id().as_deref() == Some(DO_NOT_TRACK) {
+            TelemetryStatus::DisabledByUser
+        } else {
+            TelemetryStatus::Enabled
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        *self == TelemetryStatus::Enabled
+    }
+}
+
+impl fmt::Display for TelemetryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            TelemetryStatus::Enabled => "enabled",
+            TelemetryStatus::DisabledByFlag => "disabled by --no-telemetry",
+            TelemetryStatus::DisabledByEnvironment => "disabled by SCOUT_NO_TELEMETRY",
+            TelemetryStatus::DisabledByConfig => {
+                "disabled by `telemetry: false` in the project configuration"
+            }
+            TelemetryStatus::DisabledByUser => "disabled by `cargo scout-audit telemetry disable`",
+        };
+        write!(f, "{}", message)
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    !matches!(
+        value.trim().to_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
 
 pub struct TelemetryClient {
     report: ReportDto,
@@ -74,15 +132,31 @@ impl TelemetryClient {
         }
     }
 
+    fn telemetry_directory() -> PathBuf {
+        get_config_directory().join("telemetry")
+    }
+
+    fn user_id_path() -> PathBuf {
+        Self::telemetry_directory().join("user_id.txt")
+    }
+
+    fn reports_directory() -> PathBuf {
+        Self::telemetry_directory().join("reports")
+    }
+
+    // The user ID saved by a previous run, if any.
+    pub fn stored_user_id() -> Option<String> {
+        let content = fs::read_to_string(Self::user_id_path()).ok()?;
+        let last_line = content.lines().last()?.trim();
+        (!last_line.is_empty()).then(|| last_line.to_string())
+    }
+
     fn get_user_id() -> String {
-        let user_id_path = get_config_directory().join("telemetry").join("user_id.txt");
+        let user_id_path = Self::user_id_path();
 
         // Read user ID from file
-        if let Ok(content) = fs::read_to_string(&user_id_path)
-            && let Some(last_line) = content.lines().last()
-            && !last_line.trim().is_empty()
-        {
-            return last_line.to_string();
+        if let Some(user_id) = Self::stored_user_id() {
+            return user_id;
         }
 
         // Create parent directory if it doesn't exist
@@ -90,7 +164,7 @@ impl TelemetryClient {
             && let Err(e) = fs::create_dir_all(parent)
         {
             tracing::error!("Failed to create telemetry directory: {}", e);
-            return "DONOTTRACK".to_string();
+            return DO_NOT_TRACK.to_string();
         }
 
         // Request new user ID from server
@@ -114,7 +188,7 @@ impl TelemetryClient {
             }
             Err(e) => {
                 tracing::warn!("Failed to get user ID: {}", e);
-                "DONOTTRACK".to_string()
+                DO_NOT_TRACK.to_string()
             }
         }
     }
@@ -144,7 +218,7 @@ impl TelemetryClient {
     }
 
     pub fn send_report(&self) -> Result<()> {
-        if self.report.user_id.is_empty() || self.report.user_id.eq("DONOTTRACK") {
+        if self.report.user_id.is_empty() || self.report.user_id.eq(DO_NOT_TRACK) {
             tracing::info!("Telemetry is disabled");
             return Ok(());
         }
@@ -156,7 +230,7 @@ impl TelemetryClient {
             .send()
             .context("Failed to send telemetry report")?;
 
-        let reports_dir = get_config_directory().join("telemetry").join("reports");
+        let reports_dir = Self::reports_directory();
         fs::create_dir_all(&reports_dir)?;
 
         let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
@@ -166,4 +240,64 @@ impl TelemetryClient {
 
         Ok(())
     }
+
+    // The report a run would send, without contacting the server. Users that
+    // have no ID yet get one assigned by the server on their first report.
+    pub fn preview(blockchain: Option<BlockChain>, client_type: ClientType) -> serde_json::Value {
+        serde_json::json!({
+            "user_id": Self::stored_user_id()
+                .unwrap_or_else(|| "<assigned on the first report>".to_string()),
+            "scout_version": env!("CARGO_PKG_VERSION"),
+            "crate_type": blockchain
+                .map(|blockchain| blockchain.to_string())
+                .unwrap_or_else(|| "<detected from the analyzed project>".to_string()),
+            "client_type": client_type,
+            "os": Os::from(env::consts::OS),
+        })
+    }
+
+    // Opts out and forgets the user ID, along with the local copies of the
+    // reports, which contain it.
+    pub fn disable() -> Result<Option<String>> {
+        let previous_user_id = Self::stored_user_id().filter(|id| id != DO_NOT_TRACK);
+
+        fs::create_dir_all(Self::telemetry_directory())?;
+        fs::write(Self::user_id_path(), DO_NOT_TRACK)
+            .context("Failed to write telemetry user ID file")?;
+
+        let reports_dir = Self::reports_directory();
+        if reports_dir.exists() {
+            fs::remove_dir_all(&reports_dir).context("Failed to remove telemetry reports")?;
+        }
+
+        Ok(previous_user_id)
+    }
+
+    // Removes the opt-out, so the next run requests a new user ID.
+    pub fn enable() -> Result<()> {
+        if Self::stored_user_id().as_deref() == Some(DO_NOT_TRACK) {
+            fs::remove_file(Self::user_id_path())
+                .context("Failed to remove telemetry user ID file")?;
+        }
+        Ok(())
+    }
+
+    pub fn delete_data_url(user_id: &str) -> String {
+        format!("{}/user/delete/{}", SCOUT_TELEMETRY_URL, user_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_truthy;
+
+    #[test]
+    fn environment_values() {
+        for value in ["1", "true", "yes", "TRUE"] {
+            assert!(is_truthy(value), "{}", value);
+        }
+        for value in ["", "0", "false", "No", "off"] {
+            assert!(!is_truthy(value), "{}", value);
+        }
+    }
 }
@@ -0,0 +1,218 @@
+use crate::scout::finding::Finding;
+use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+const TRIAGE_VERSION: u32 = 1;
+
+// Fingerprints can be shortened, as long as they stay this long. The Markdown
+// report shows this many characters.
+pub const MIN_FINGERPRINT_LENGTH: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriageStatus {
+    FalsePositive,
+    AcceptedRisk,
+    WontFix,
+}
+
+impl fmt::Display for TriageStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            TriageStatus::FalsePositive => "false-positive",
+            TriageStatus::AcceptedRisk => "accepted-risk",
+            TriageStatus::WontFix => "wont-fix",
+        };
+        write!(f, "{}", status)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TriageEntry {
+    pub fingerprint: String,
+    pub status: TriageStatus,
+    pub author: String,
+    #[serde(default)]
+    pub comment: String,
+    pub date: String,
+}
+
+// Statuses given to findings by the auditors, kept in
+// `.scout-audit/triage.yaml` at the workspace root so they can be committed.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TriageStore {
+    pub version: u32,
+    #[serde(default)]
+    pub findings: Vec<TriageEntry>,
+}
+
+impl Default for TriageStore {
+    fn default() -> Self {
+        TriageStore {
+            version: TRIAGE_VERSION,
+            findings: Vec::new(),
+        }
+    }
+}
+
+impl TriageStore {
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(".scout-audit").join("triage.yaml")
+    }
+
+    // A project without a triage file has nothing triaged.
+    pub fn load(workspace_root: &Path) -> Result<Self> {
+        let path = Self::path(workspace_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read triage file: {}", path.display()))?;
+        let store: TriageStore = serde_yaml::from_str(&contents)
+            .with_context(|| format!("Failed to parse triage file: {}", path.display()))?;
+        if store.version > TRIAGE_VERSION {
+            bail!(
+                "Triage file version {} is not supported by this version of scout (max {})",
+                store.version,
+                TRIAGE_VERSION
+            );
+        }
+        Ok(store)
+    }
+
+    pub fn save(&self, workspace_root: &Path) -> Result<PathBuf> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write triage file: {}", path.display()))?;
+        Ok(path)
+    }
+
+    // Replaces any previous status of the same fingerprint.
+    pub fn mark(&mut self, entry: TriageEntry) -> Result<()> {
+        validate_fingerprint(&entry.fingerprint)?;
+        self.findings
+            .retain(|known| !same_fingerprint(&known.fingerprint, &entry.fingerprint));
+        self.findings.push(entry);
+        Ok(())
+    }
+
+    // Returns whether the fingerprint was triaged.
+    pub fn unmark(&mut self, fingerprint: &str) -> bool {
+        let before = self.findings.len();
+        self.findings
+            .retain(|known| !same_fingerprint(&known.fingerprint, fingerprint));
+        self.findings.len() != before
+    }
+
+    pub fn find(&self, fingerprint: &str) -> Option<&TriageEntry> {
+        if fingerprint.is_empty() {
+            return None;
+        }
+        self.findings
+            .iter()
+            .find(|entry| fingerprint.starts_with(entry.fingerprint.as_str()))
+    }
+
+    // Splits the findings into the untriaged ones and those with a status.
+    pub fn apply(&self, findings: Vec<Finding>) -> (Vec<Finding>, Vec<(Finding, TriageEntry)>) {
+        if self.findings.is_empty() {
+            return (findings, Vec::new());
+        }
+        let mut untriaged = Vec::new();
+        let mut triaged = Vec::new();
+        for finding in findings {
+            match self.find(finding.fingerprint()) {
+                Some(entry) => triaged.push((finding, entry.clone())),
+                None => untriaged.push(finding),
+            }
+        }
+        (untriaged, triaged)
+    }
+}
+
+// The git user name, falling back to the login name.
+pub fn default_author() -> String {
+    git2::Config::open_default()
+        .and_then(|config| config.get_string("user.name"))
+        .ok()
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// Counts the triaged findings by status, for the console summary.
+pub fn count_by_status(triaged: &[(Finding, TriageEntry)]) -> BTreeMap<TriageStatus, usize> {
+    let mut counts = BTreeMap::new();
+    for (_, entry) in triaged {
+        *counts.entry(entry.status).or_insert(0) += 1;
+    }
+    counts
+}
+
+// Either fingerprint may be a prefix of the other.
+fn same_fingerprint(a: &str, b: &str) -> bool {
+    a.starts_with(b) || b.starts_with(a)
+}
+
+fn validate_fingerprint(fingerprint: &str) -> Result<()> {
+    if fingerprint.len() < MIN_FINGERPRINT_LENGTH
+        || !fingerprint.chars().all(|c| c.is_ascii_hexdigit())
+    {
+        bail!(
+            "'{}' is not a finding fingerprint. Use at least {} hexadecimal characters of the fingerprint shown in the reports",
+            fingerprint,
+            MIN_FINGERPRINT_LENGTH
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TriageEntry, TriageStatus, TriageStore};
+
+    fn entry(fingerprint: &str, status: TriageStatus) -> TriageEntry {
+        TriageEntry {
+            fingerprint: fingerprint.to_string(),
+            status,
+            author: "auditor".to_string(),
+            comment: String::new(),
+            date: "2026-10-18".to_string(),
+        }
+    }
+
+    #[test]
+    fn marks_by_prefix_and_replaces_statuses() {
+        let directory = tempfile::TempDir::new().unwrap();
+        let fingerprint = "0123456789abcdef0123456789abcdef";
+
+        let mut store = TriageStore::default();
+        store
+            .mark(entry(&fingerprint[..12], TriageStatus::FalsePositive))
+            .unwrap();
+        store
+            .mark(entry(fingerprint, TriageStatus::AcceptedRisk))
+            .unwrap();
+        assert!(store.mark(entry("0123", TriageStatus::WontFix)).is_err());
+        store.save(directory.path()).unwrap();
+
+        let store = TriageStore::load(directory.path()).unwrap();
+        assert_eq!(store.findings.len(), 1);
+        assert_eq!(
+            store.find(fingerprint).map(|entry| entry.status),
+            Some(TriageStatus::AcceptedRisk)
+        );
+        assert!(store.find("fedcba9876543210").is_none());
+        assert!(store.find("").is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::util::{home::get_config_directory, logger::TracedError};
+use crate::util::{home::get_config_directory, logger::TracedError, print::is_quiet};
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
@@ -146,6 +146,8 @@ impl VersionChecker {
             command.cyan()
         );
 
-        eprintln!("{}", message.yellow());
+        if !is_quiet() {
+            eprintln!("{}", message.yellow());
+        }
     }
 }
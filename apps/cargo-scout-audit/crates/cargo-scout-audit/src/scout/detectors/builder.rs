@@ -59,9 +59,28 @@ impl<'a> DetectorBuilder<'a> {
 
     #[tracing::instrument(skip_all, level = "debug")]
     pub fn build(&self, used_detectors: &[String]) -> Result<Vec<PathBuf>> {
+        self.build_with_progress(used_detectors, &mut |_| {})
+    }
+
+    // Like `build`, calling `on_built` with each used detector as soon as its
+    // library is compiled.
+    pub fn build_with_progress(
+        &self,
+        used_detectors: &[String],
+        on_built: &mut dyn FnMut(&Path),
+    ) -> Result<Vec<PathBuf>> {
         print_info("Compiling detectors...");
-        let all_library_paths = self.build_all_libraries()?;
-        self.filter_detectors(&all_library_paths, used_detectors)
+        let mut detector_paths = Vec::new();
+        for library in self.get_all_libraries()? {
+            let library_paths = library
+                .build(self.verbose)
+                .map_err(BuilderError::BuildError(library.lib.root).traced())?;
+            for path in self.filter_detectors(&library_paths, used_detectors)? {
+                on_built(&path);
+                detector_paths.push(path);
+            }
+        }
+        Ok(detector_paths)
     }
 
     #[tracing::instrument(skip_all, level = "debug")]
@@ -77,22 +96,6 @@ impl<'a> DetectorBuilder<'a> {
         Ok(all_names)
     }
 
-    #[tracing::instrument(skip_all, level = "debug")]
-    fn build_all_libraries(&self) -> Result<Vec<PathBuf>> {
-        let mut all_library_paths = Vec::new();
-
-        let libraries = self.get_all_libraries()?;
-
-        for library in libraries {
-            let library_paths = library
-                .build(self.verbose)
-                .map_err(BuilderError::BuildError(library.lib.root).traced())?;
-            all_library_paths.extend(library_paths);
-        }
-
-        Ok(all_library_paths)
-    }
-
     #[tracing::instrument(skip_all, level = "debug")]
     fn get_all_libraries(&self) -> Result<Vec<DetectorLibrary>> {
         let mut all_libraries = Vec::new();
@@ -181,13 +184,14 @@ impl<'a> DetectorBuilder<'a> {
         path.file_name()
             .and_then(|name| name.to_str())
             .map(|name| {
-                let name = self.normalize_detector_name(name);
+                let name = Self::normalize_detector_name(name);
                 used_detectors.contains(&name)
             })
             .unwrap_or(false)
     }
 
-    fn normalize_detector_name(&self, name: &str) -> String {
+    // Maps a compiled detector file name to the detector it contains.
+    pub fn normalize_detector_name(name: &str) -> String {
         #[cfg(not(windows))]
         let name = name.strip_prefix(Self::LIB_PREFIX).unwrap_or(name);
 
@@ -124,12 +124,17 @@ impl DetectorsConfiguration {
             Self::get_detector_path(toolchain, "rust"),
         )?;
 
-        let blockchain_config = DetectorConfig::with_dependency_and_path(
-            source_id,
-            Self::get_detector_path(toolchain, blockchain.get_detectors_path()),
-        )?;
-
-        Ok(Self::new(base_config, Some(blockchain_config)))
+        let blockchain_config = blockchain
+            .get_detectors_path()
+            .map(|path| {
+                DetectorConfig::with_dependency_and_path(
+                    source_id,
+                    Self::get_detector_path(toolchain, path),
+                )
+            })
+            .transpose()?;
+
+        Ok(Self::new(base_config, blockchain_config))
     }
 
     /// Returns local detectors configuration from custom path.
@@ -153,10 +158,12 @@ impl DetectorsConfiguration {
         let base_config =
             DetectorConfig::with_dependency_and_path(source_id, LOCAL_BASE_DETECTOR_PATH)?;
 
-        let blockchain_config =
-            DetectorConfig::with_dependency_and_path(source_id, blockchain.to_string())?;
+        let blockchain_config = blockchain
+            .get_detectors_path()
+            .map(|path| DetectorConfig::with_dependency_and_path(source_id, path))
+            .transpose()?;
 
-        Ok(Self::new(base_config, Some(blockchain_config)))
+        Ok(Self::new(base_config, blockchain_config))
     }
 }
 
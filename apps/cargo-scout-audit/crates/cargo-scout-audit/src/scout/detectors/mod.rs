@@ -1,3 +1,4 @@
 pub mod builder;
 pub mod configuration;
 pub mod library;
+pub mod source;
@@ -0,0 +1,76 @@
+use super::builder::DetectorBuilder;
+use crate::{
+    bundle::DetectorBundle,
+    cli_args::BlockChain,
+    detector_helper::{build_detector_helper, get_detectors_info},
+    scout_driver::build_scout_driver,
+    util::detectors_info::LintStore,
+};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+// Where the detectors and drivers of a run come from: built from their
+// sources, or taken prebuilt from an imported bundle in offline mode.
+pub enum DetectorSource<'a> {
+    Build {
+        builder: DetectorBuilder<'a>,
+        scout_source: Option<PathBuf>,
+    },
+    Bundle {
+        bundle: &'a DetectorBundle,
+        blockchain: BlockChain,
+    },
+}
+
+impl DetectorSource<'_> {
+    pub fn get_detector_names(&self) -> Result<Vec<String>> {
+        match self {
+            DetectorSource::Build { builder, .. } => builder.get_detector_names(),
+            DetectorSource::Bundle { bundle, blockchain } => bundle.detector_names(*blockchain),
+        }
+    }
+
+    // Calls `on_built` with each detector as soon as it is ready to use.
+    pub fn build(
+        &self,
+        used_detectors: &[String],
+        on_built: &mut dyn FnMut(&Path),
+    ) -> Result<Vec<PathBuf>> {
+        match self {
+            DetectorSource::Build { builder, .. } => {
+                builder.build_with_progress(used_detectors, on_built)
+            }
+            DetectorSource::Bundle { bundle, blockchain } => {
+                let paths = bundle.detector_paths(*blockchain, used_detectors)?;
+                paths.iter().for_each(|path| on_built(path));
+                Ok(paths)
+            }
+        }
+    }
+
+    pub fn get_detectors_info(
+        &self,
+        toolchain: &str,
+        detectors_paths: &[PathBuf],
+        used_detectors: &[String],
+    ) -> Result<LintStore> {
+        match self {
+            DetectorSource::Build { scout_source, .. } => {
+                let helper_path = build_detector_helper(toolchain, scout_source.as_ref())?;
+                get_detectors_info(toolchain, &helper_path, detectors_paths)
+            }
+            DetectorSource::Bundle { bundle, blockchain } => {
+                bundle.detectors_info(*blockchain, used_detectors)
+            }
+        }
+    }
+
+    pub fn get_scout_driver(&self, toolchain: &str) -> Result<PathBuf> {
+        match self {
+            DetectorSource::Build { scout_source, .. } => {
+                build_scout_driver(toolchain, scout_source.as_ref())
+            }
+            DetectorSource::Bundle { bundle, .. } => Ok(bundle.scout_driver()),
+        }
+    }
+}
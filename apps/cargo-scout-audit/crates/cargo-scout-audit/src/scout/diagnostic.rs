@@ -0,0 +1,171 @@
+use serde::{Deserialize, Serialize};
+
+// The `message` of a cargo `compiler-message`, as rustc prints it with
+// `--error-format=json`. Only the fields scout uses are kept, and missing ones
+// take their defaults, so hand-written or truncated messages still parse.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Diagnostic {
+    pub message: String,
+    pub code: Option<DiagnosticCode>,
+    pub level: DiagnosticLevel,
+    pub spans: Vec<DiagnosticSpan>,
+    pub children: Vec<Diagnostic>,
+    pub rendered: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DiagnosticCode {
+    // The lint name for detector findings, e.g. `unsafe_unwrap`
+    pub code: String,
+    pub explanation: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DiagnosticLevel {
+    #[serde(rename = "error: internal compiler error")]
+    Ice,
+    Error,
+    #[default]
+    Warning,
+    Note,
+    Help,
+    FailureNote,
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DiagnosticSpan {
+    pub file_name: String,
+    pub byte_start: u32,
+    pub byte_end: u32,
+    // Lines and columns are 1-based
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+    pub is_primary: bool,
+    pub label: Option<String>,
+    pub suggested_replacement: Option<String>,
+    pub suggestion_applicability: Option<Applicability>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Applicability {
+    MachineApplicable,
+    MaybeIncorrect,
+    HasPlaceholders,
+    #[serde(other)]
+    Unspecified,
+}
+
+impl Diagnostic {
+    // The detector (or rustc lint) that raised the diagnostic, if any.
+    pub fn detector(&self) -> Option<&str> {
+        self.code.as_ref().map(|code| code.code.as_str())
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self.level, DiagnosticLevel::Error | DiagnosticLevel::Ice)
+    }
+
+    // The span the diagnostic is reported at: the first primary one, or the
+    // first one if rustc marked none as primary.
+    pub fn span(&self) -> Option<&DiagnosticSpan> {
+        self.spans
+            .iter()
+            .find(|span| span.is_primary)
+            .or_else(|| self.spans.first())
+    }
+
+    // Every span but the main one, pointing at related code.
+    pub fn secondary_spans(&self) -> impl Iterator<Item = &DiagnosticSpan> {
+        let main = self.span();
+        self.spans
+            .iter()
+            .filter(move |span| !main.is_some_and(|main| std::ptr::eq(main, *span)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Applicability, Diagnostic, DiagnosticLevel};
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[test]
+    fn parses_rustc_diagnostics() {
+        let diagnostic = Diagnostic::deserialize(&json!({
+            "$message_type": "diagnostic",
+            "message": "Unsafe usage of `unwrap`",
+            "code": { "code": "unsafe_unwrap", "explanation": null },
+            "level": "warning",
+            "spans": [
+                { "file_name": "src/lib.rs", "line_start": 3, "label": "declared here" },
+                {
+                    "file_name": "src/lib.rs", "byte_start": 120, "byte_end": 135,
+                    "line_start": 7, "line_end": 7, "column_start": 9, "column_end": 24,
+                    "is_primary": true, "text": [], "label": null,
+                    "suggested_replacement": null, "suggestion_applicability": null,
+                    "expansion": null
+                }
+            ],
+            "children": [{
+                "message": "use `unwrap_or` instead",
+                "code": null,
+                "level": "help",
+                "spans": [{
+                    "file_name": "src/lib.rs", "line_start": 7,
+                    "suggested_replacement": "x.unwrap_or(0)",
+                    "suggestion_applicability": "MaybeIncorrect"
+                }],
+                "children": [],
+                "rendered": null
+            }],
+            "rendered": "warning: Unsafe usage of `unwrap`\n"
+        }))
+        .unwrap();
+
+        assert_eq!(diagnostic.detector(), Some("unsafe_unwrap"));
+        assert_eq!(diagnostic.level, DiagnosticLevel::Warning);
+        assert!(!diagnostic.is_error());
+        let span = diagnostic.span().unwrap();
+        assert_eq!(
+            (span.byte_start, span.line_start, span.column_end),
+            (120, 7, 24)
+        );
+        let secondary = diagnostic.secondary_spans().collect::<Vec<_>>();
+        assert_eq!(secondary.len(), 1);
+        assert_eq!(secondary[0].label.as_deref(), Some("declared here"));
+        let span = &diagnostic.children[0].spans[0];
+        assert_eq!(
+            span.suggested_replacement.as_deref(),
+            Some("x.unwrap_or(0)")
+        );
+        assert_eq!(
+            span.suggestion_applicability,
+            Some(Applicability::MaybeIncorrect)
+        );
+    }
+
+    #[test]
+    fn parses_compiler_errors() {
+        let diagnostic = Diagnostic::deserialize(&json!({
+            "message": "aborting due to 1 previous error",
+            "level": "error",
+        }))
+        .unwrap();
+        assert!(diagnostic.is_error());
+        assert_eq!(diagnostic.detector(), None);
+        assert!(diagnostic.span().is_none());
+
+        let ice = Diagnostic::deserialize(&json!({ "level": "error: internal compiler error" }));
+        assert!(ice.unwrap().is_error());
+        let unknown = Diagnostic::deserialize(&json!({ "level": "lint-expectation" }));
+        assert_eq!(unknown.unwrap().level, DiagnosticLevel::Unknown);
+    }
+}
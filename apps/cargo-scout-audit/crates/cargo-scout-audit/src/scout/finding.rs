@@ -1,16 +1,60 @@
+use crate::scout::core::{
+    cache::FROM_CACHE_FIELD,
+    fingerprint::{finding_fingerprint, finding_snippet},
+};
+use crate::scout::diagnostic::{Diagnostic, DiagnosticSpan};
 use crate::util::json::{json_to_string_exact, json_to_string_opt};
+use anyhow::{Context, Result};
+use serde::Deserialize;
 use serde_json::Value;
-use std::collections::HashSet;
-use std::result::Result;
+use std::{collections::HashSet, path::Path};
 
+// A line of cargo's JSON output. The message is kept as is, so it can be
+// cached and passed on unchanged, and the diagnostic of `compiler-message`
+// lines is parsed once here.
 #[derive(Clone, Debug)]
 pub struct Finding {
     value: Value,
+    diagnostic: Option<Diagnostic>,
+    // Set by `fingerprinted`, as it needs the workspace sources
+    snippet: String,
+    fingerprint: String,
 }
 
 impl Finding {
+    // Fails when the message of a `compiler-message` does not fit the
+    // diagnostic model, rather than dropping it and its finding.
+    pub fn parse(value: Value) -> Result<Finding> {
+        let diagnostic = value
+            .get("message")
+            .filter(|message| message.is_object())
+            .map(Diagnostic::deserialize)
+            .transpose()
+            .context("The compiler message is not a valid diagnostic")?;
+        Ok(Finding {
+            value,
+            diagnostic,
+            snippet: String::new(),
+            fingerprint: String::new(),
+        })
+    }
+    // Reads the flagged code and fingerprints the finding, once per run.
+    pub fn fingerprinted(mut self, workspace_root: &Path) -> Finding {
+        self.snippet = finding_snippet(&self, workspace_root);
+        self.fingerprint = finding_fingerprint(&self, workspace_root, &self.snippet);
+        self
+    }
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+    // The flagged code, empty if the file is no longer available.
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+    // Like `parse`, but panics if the message is not a valid diagnostic.
+    // Kept for existing callers until the next breaking release.
     pub fn new(value: Value) -> Finding {
-        Finding { value }
+        Self::parse(value).unwrap()
     }
     pub fn reason(&self) -> String {
         self.value
@@ -18,14 +62,15 @@ impl Finding {
             .and_then(json_to_string_exact)
             .unwrap_or_default()
     }
-    fn code_helper(&self) -> Result<String, ()> {
-        let message = self.value.get("message").ok_or(())?;
-        let code = message.get("code").ok_or(())?;
-        let code = code.get("code").ok_or(())?;
-        json_to_string_exact(code).ok_or(())
+    pub fn diagnostic(&self) -> Option<&Diagnostic> {
+        self.diagnostic.as_ref()
     }
     pub fn code(&self) -> String {
-        self.code_helper().unwrap_or_else(|_| String::new())
+        self.diagnostic
+            .as_ref()
+            .and_then(Diagnostic::detector)
+            .unwrap_or_default()
+            .to_string()
     }
     pub fn dashed_code(&self) -> String {
         self.code().replace("_", "-")
@@ -34,15 +79,8 @@ impl Finding {
         self.reason() == "compiler-message" && filtered_detectors.contains(&self.dashed_code())
     }
     pub fn is_compiler_error(&self) -> bool {
-        if self.reason() != "compiler-message" {
-            return false;
-        }
-        self.value
-            .get("message")
-            .and_then(|x| x.get("level"))
-            .and_then(json_to_string_exact)
-            .map(|x| x == "error")
-            .unwrap_or(false)
+        self.reason() == "compiler-message"
+            && self.diagnostic.as_ref().is_some_and(Diagnostic::is_error)
     }
     pub fn package(&self) -> String {
         json_to_string_opt(self.value.get("target").and_then(|x| x.get("name"))).unwrap_or_default()
@@ -50,6 +88,13 @@ impl Finding {
     pub fn package_id(&self) -> String {
         json_to_string_opt(self.value.get("package_id")).unwrap_or_default()
     }
+    // Whether the message was read back from the findings cache.
+    pub fn from_cache(&self) -> bool {
+        self.value
+            .get(FROM_CACHE_FIELD)
+            .and_then(Value::as_bool)
+            .unwrap_or(false)
+    }
     pub fn krate(&self) -> String {
         self.package().replace("_", "-")
     }
@@ -65,21 +110,37 @@ impl Finding {
             .and_then(|x| x.get("spans"))
             .cloned()
     }
+    // The span the finding is reported at, see `Diagnostic::span`.
+    pub fn span(&self) -> Option<&DiagnosticSpan> {
+        self.diagnostic.as_ref()?.span()
+    }
+    pub fn secondary_spans(&self) -> Vec<&DiagnosticSpan> {
+        self.diagnostic
+            .as_ref()
+            .map(|diagnostic| diagnostic.secondary_spans().collect())
+            .unwrap_or_default()
+    }
+    pub fn file_name(&self) -> Option<String> {
+        self.span()
+            .map(|span| span.file_name.clone())
+            .filter(|file_name| !file_name.is_empty())
+    }
     pub fn message(&self) -> String {
-        self.value
-            .get("message")
-            .and_then(|x| x.get("message"))
-            .and_then(json_to_string_exact)
+        self.diagnostic
+            .as_ref()
+            .map(|diagnostic| diagnostic.message.clone())
             .unwrap_or_default()
     }
     pub fn rendered(&self) -> String {
-        self.value
-            .get("message")
-            .and_then(|x| x.get("rendered"))
-            .and_then(json_to_string_exact)
+        self.diagnostic
+            .as_ref()
+            .and_then(|diagnostic| diagnostic.rendered.clone())
             .unwrap_or_default()
     }
-    pub fn children(&self) -> Option<Value> {
-        Some(self.value.get("message")?.get("children")?.clone())
+    pub fn children(&self) -> &[Diagnostic] {
+        self.diagnostic
+            .as_ref()
+            .map(|diagnostic| diagnostic.children.as_slice())
+            .unwrap_or_default()
     }
 }
@@ -1,4 +1,5 @@
 pub mod core;
 pub mod detectors;
+pub mod diagnostic;
 pub mod finding;
 pub mod output;
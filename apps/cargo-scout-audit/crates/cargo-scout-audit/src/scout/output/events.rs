@@ -0,0 +1,224 @@
+use crate::{
+    cli_args::EventFormat,
+    scout::{
+        core::triage::{TriageStatus, TriageStore},
+        finding::Finding,
+        output::report::Severity,
+    },
+    util::{
+        detectors_info::LintStore,
+        print::{reserve_stdout, strip_ansi},
+    },
+};
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+// Sent with every event. It only changes when an event or field is removed or
+// changes meaning; new events and fields may appear within a version.
+pub const EVENTS_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum Event {
+    DetectorsBuilding {
+        detectors: usize,
+        offline: bool,
+    },
+    DetectorBuilt {
+        name: String,
+        path: PathBuf,
+    },
+    CrateStarted {
+        name: String,
+        cached: bool,
+    },
+    CrateFinished {
+        name: String,
+        success: bool,
+        cached: bool,
+        findings: usize,
+    },
+    Finding(FindingEvent),
+    Summary(SummaryEvent),
+    Error {
+        message: String,
+        causes: Vec<String>,
+    },
+}
+
+#[derive(Serialize, Debug)]
+pub struct FindingEvent {
+    pub fingerprint: String,
+    pub detector: String,
+    pub severity: Option<Severity>,
+    pub category: String,
+    pub package: String,
+    pub file_path: String,
+    pub line_start: u64,
+    pub column_start: u64,
+    pub line_end: u64,
+    pub column_end: u64,
+    pub message: String,
+    // The diagnostic as rustc prints it, without colors
+    pub rendered: String,
+    pub triage: Option<TriageStatus>,
+}
+
+impl FindingEvent {
+    pub fn new(finding: &Finding, detectors_info: &LintStore, triage: &TriageStore) -> Self {
+        let detector = finding.code();
+        let lint = detectors_info.find_by_id(&detector);
+        let fingerprint = finding.fingerprint().to_string();
+        let span = finding.span().cloned().unwrap_or_default();
+        FindingEvent {
+            triage: triage.find(&fingerprint).map(|entry| entry.status),
+            fingerprint,
+            severity: lint.and_then(|lint| Severity::from_lint_severity(&lint.severity)),
+            category: lint
+                .map(|lint| lint.vulnerability_class.clone())
+                .unwrap_or_default(),
+            detector,
+            package: finding.package(),
+            file_path: finding.file_name().unwrap_or_default().replace('\\', "/"),
+            line_start: span.line_start as u64,
+            column_start: span.column_start as u64,
+            line_end: span.line_end as u64,
+            column_end: span.column_end as u64,
+            message: finding.message(),
+            rendered: strip_ansi(&finding.rendered()),
+        }
+    }
+}
+
+// Sent last, once the reports are written.
+#[derive(Serialize, Debug, Default)]
+pub struct SummaryEvent {
+    // Findings reported by this run, i.e. not hidden by any of the below
+    pub findings: usize,
+    pub by_severity: BTreeMap<Severity, usize>,
+    pub suppressed: usize,
+    pub excluded_by_path: u32,
+    pub known_from_baseline: usize,
+    pub pre_existing: usize,
+    pub triaged: usize,
+    pub failed_crates: Vec<String>,
+    pub threshold_exceeded: bool,
+}
+
+// Writes `--events` to stdout, one event per line. Everything else printed
+// to stdout moves to stderr while it is enabled.
+#[derive(Clone, Copy, Debug)]
+pub struct EventStream {
+    format: Option<EventFormat>,
+}
+
+impl EventStream {
+    pub fn new(format: Option<EventFormat>) -> Self {
+        if format.is_some() {
+            reserve_stdout();
+        }
+        EventStream { format }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.format.is_some()
+    }
+
+    pub fn emit(&self, event: Event) {
+        let Some(EventFormat::Jsonl) = self.format else {
+            return;
+        };
+        let mut stdout = std::io::stdout().lock();
+        // A consumer that went away is no reason to fail the analysis
+        let _ = writeln!(stdout, "{}", event_line(&event));
+        let _ = stdout.flush();
+    }
+
+    pub fn error(&self, error: &anyhow::Error) {
+        self.emit(Event::Error {
+            message: error.to_string(),
+            causes: error.chain().skip(1).map(ToString::to_string).collect(),
+        });
+    }
+}
+
+fn event_line(event: &Event) -> String {
+    #[derive(Serialize)]
+    struct Envelope<'a> {
+        schema: u32,
+        #[serde(flatten)]
+        event: &'a Event,
+    }
+    serde_json::to_string(&Envelope {
+        schema: EVENTS_SCHEMA_VERSION,
+        event,
+    })
+    .unwrap_or_default()
+}
+
+// Detector libraries are named like `libunsafe_unwrap@nightly-2025-08-07-x86_64-unknown-linux-gnu.so`.
+pub fn detector_name(path: &Path) -> String {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let name = file_name.split(['@', '.']).next().unwrap_or_default();
+    name.strip_prefix(std::env::consts::DLL_PREFIX)
+        .unwrap_or(name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, SummaryEvent, detector_name, event_line};
+    use crate::scout::output::report::Severity;
+    use serde_json::{Value, json};
+    use std::path::Path;
+
+    #[test]
+    fn writes_versioned_events() {
+        let line = event_line(&Event::CrateFinished {
+            name: "token".to_string(),
+            success: true,
+            cached: false,
+            findings: 2,
+        });
+        assert_eq!(
+            serde_json::from_str::<Value>(&line).unwrap(),
+            json!({
+                "schema": 1,
+                "event": "crate-finished",
+                "name": "token",
+                "success": true,
+                "cached": false,
+                "findings": 2,
+            })
+        );
+
+        let mut summary = SummaryEvent {
+            findings: 1,
+            ..Default::default()
+        };
+        summary.by_severity.insert(Severity::Critical, 1);
+        let line = event_line(&Event::Summary(summary));
+        assert!(!line.contains('\n'));
+        let value = serde_json::from_str::<Value>(&line).unwrap();
+        assert_eq!(value["event"], "summary");
+        assert_eq!(value["by_severity"], json!({ "critical": 1 }));
+    }
+
+    #[test]
+    fn names_detector_libraries() {
+        let path = Path::new(&format!(
+            "/tmp/{}unsafe_unwrap@nightly-2025-08-07-x86_64-unknown-linux-gnu{}",
+            std::env::consts::DLL_PREFIX,
+            std::env::consts::DLL_SUFFIX
+        ))
+        .to_path_buf();
+        assert_eq!(detector_name(&path), "unsafe_unwrap");
+    }
+}
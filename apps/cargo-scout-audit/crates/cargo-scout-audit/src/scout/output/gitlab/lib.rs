@@ -0,0 +1,100 @@
+use super::model::{Issue, IssueSeverity, Lines, Location};
+use crate::{
+    scout::{
+        finding::Finding as JsonFinding,
+        output::report::{Report, Severity},
+    },
+    util::detectors_info::LintStore,
+};
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+// Generates a GitLab Code Quality (Code Climate) report from a given `Report`
+// object.
+pub fn generate_gitlab_code_quality(
+    report: &Report,
+    findings: &[JsonFinding],
+    detectors_info: &LintStore,
+) -> Result<String> {
+    let mut seen = HashMap::<String, u32>::new();
+    let issues = report
+        .untriaged_findings()
+        .filter_map(|finding| {
+            let json_finding = findings.get(finding.id as usize)?;
+            let lint = detectors_info.find_by_id(&finding.vulnerability_id)?;
+            let file_path = json_finding
+                .file_name()
+                .unwrap_or_default()
+                .replace('\\', "/");
+            let (begin, end) = lines(json_finding).unwrap_or((1, 1));
+
+            Some(Issue {
+                kind: "issue",
+                check_name: lint.id.clone(),
+                description: finding.error_message.clone(),
+                categories: vec!["Security"],
+                severity: severity(Severity::from_lint_severity(&lint.severity)),
+                fingerprint: disambiguate(finding.fingerprint.clone(), &mut seen),
+                location: Location {
+                    path: file_path,
+                    lines: Lines { begin, end },
+                },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    Ok(serde_json::to_string_pretty(&issues)?)
+}
+
+fn severity(severity: Option<Severity>) -> IssueSeverity {
+    match severity {
+        Some(Severity::Critical) => IssueSeverity::Critical,
+        Some(Severity::Medium) | None => IssueSeverity::Major,
+        Some(Severity::Minor) => IssueSeverity::Minor,
+        Some(Severity::Enhancement) => IssueSeverity::Info,
+    }
+}
+
+// GitLab merges issues with the same fingerprint, so repeated occurrences of
+// the same code in a file get the occurrence number mixed in. The first one
+// keeps the plain fingerprint, which matches the baseline and SARIF ones.
+fn disambiguate(fingerprint: String, seen: &mut HashMap<String, u32>) -> String {
+    let count = seen.entry(fingerprint.clone()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        fingerprint
+    } else {
+        format!(
+            "{:x}",
+            Sha256::digest(format!("{}:{}", fingerprint, count).as_bytes())
+        )
+    }
+}
+
+fn lines(finding: &JsonFinding) -> Option<(u64, u64)> {
+    let span = finding.span()?;
+    Some((
+        span.line_start as u64,
+        span.line_end.max(span.line_start) as u64,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::disambiguate;
+    use std::collections::HashMap;
+
+    #[test]
+    fn repeated_fingerprints_are_unique_and_stable() {
+        let mut seen = HashMap::new();
+        let first = disambiguate("abc".to_string(), &mut seen);
+        let second = disambiguate("abc".to_string(), &mut seen);
+        assert_eq!(first, "abc");
+        assert_ne!(second, first);
+
+        let mut seen = HashMap::new();
+        disambiguate("abc".to_string(), &mut seen);
+        assert_eq!(disambiguate("abc".to_string(), &mut seen), second);
+    }
+}
@@ -0,0 +1,36 @@
+// Subset of the Code Climate issue format read by GitLab Code Quality.
+// https://docs.gitlab.com/ci/testing/code_quality/#code-quality-report-format
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct Issue {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub check_name: String,
+    pub description: String,
+    pub categories: Vec<&'static str>,
+    pub severity: IssueSeverity,
+    pub fingerprint: String,
+    pub location: Location,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Info,
+    Minor,
+    Major,
+    Critical,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Location {
+    pub path: String,
+    pub lines: Lines,
+}
+
+#[derive(Serialize, Debug)]
+pub struct Lines {
+    pub begin: u64,
+    pub end: u64,
+}
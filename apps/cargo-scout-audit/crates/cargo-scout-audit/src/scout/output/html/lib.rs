@@ -1,11 +1,11 @@
 use super::{tera::HtmlEngine, utils};
 use crate::scout::output::report::Report;
 use anyhow::Result;
-use std::{error::Error, fmt::Write, vec};
+use std::{error::Error, fmt::Write, path::Path, vec};
 
 // Generates an HTML report from a given `Report` object.
-pub fn generate_html(report: &Report) -> Result<String> {
-    let tera = HtmlEngine::new()?;
+pub fn generate_html(report: &Report, template_dir: Option<&Path>) -> Result<String> {
+    let tera = HtmlEngine::new(template_dir)?;
 
     // Report context
     let report_context = tera.create_context("report", report);
@@ -1,4 +1,4 @@
 pub mod lib;
 pub use lib::generate_html;
-mod tera;
+pub mod tera;
 mod utils;
@@ -1,10 +1,15 @@
 extern crate tera;
 
+use crate::scout::output::utils::read_custom_templates;
+use std::path::Path;
 use tera::{Context, Result, Tera};
 
 const TEMPLATE_BASE: &str = include_str!("./templates/base.html");
+const TEMPLATE_BASELINE: &str = include_str!("./templates/baseline.html");
 const TEMPLATE_CATEGORIES: &str = include_str!("./templates/categories.html");
 const TEMPLATE_FINDINGS: &str = include_str!("./templates/findings_list.html");
+const TEMPLATE_PRE_EXISTING: &str = include_str!("./templates/pre_existing.html");
+const TEMPLATE_SUPPRESSED: &str = include_str!("./templates/suppressed.html");
 const TEMPLATE_MODAL: &str = include_str!("./templates/modal.html");
 const TEMPLATE_VULNERABILITY_DETAILS: &str = include_str!("./templates/vulnerability_details.html");
 const JS_MODAL_HANDLER: &str = include_str!("./build/modal-handler.js");
@@ -14,26 +19,40 @@ const JS_VULNERABILITY_EXPANSION: &str = include_str!("./build/vulnerability-exp
 const JS_SEVERITY_FILTER: &str = include_str!("./build/severity-filter.js");
 const STYLES: &str = include_str!("./build/styles.css");
 
+// The built-in templates, by the name they are included with. Rendering starts
+// at `base.html`.
+pub const TEMPLATES: [(&str, &str); 14] = [
+    ("base.html", TEMPLATE_BASE),
+    ("modal.html", TEMPLATE_MODAL),
+    ("categories.html", TEMPLATE_CATEGORIES),
+    ("baseline.html", TEMPLATE_BASELINE),
+    ("suppressed.html", TEMPLATE_SUPPRESSED),
+    ("pre_existing.html", TEMPLATE_PRE_EXISTING),
+    ("findings_list.html", TEMPLATE_FINDINGS),
+    ("vulnerability_details.html", TEMPLATE_VULNERABILITY_DETAILS),
+    ("modal.js", JS_MODAL_HANDLER),
+    ("category-filter.js", JS_CATEGORY_FILTER),
+    ("vulnerability-expansion.js", JS_VULNERABILITY_EXPANSION),
+    ("vulnerability-details-display.js", JS_VULNERABILITY_DETAILS),
+    ("severity-filter.js", JS_SEVERITY_FILTER),
+    ("styles.css", STYLES),
+];
+
 pub struct HtmlEngine {
     tera: Tera,
 }
 
 impl HtmlEngine {
-    pub fn new() -> Result<Self> {
+    // Files in `template_dir` replace the built-in templates with the same name,
+    // and can add new ones.
+    pub fn new(template_dir: Option<&Path>) -> Result<Self> {
         let mut tera = Tera::default();
-        tera.add_raw_templates(vec![
-            ("base.html", TEMPLATE_BASE),
-            ("modal.html", TEMPLATE_MODAL),
-            ("categories.html", TEMPLATE_CATEGORIES),
-            ("findings_list.html", TEMPLATE_FINDINGS),
-            ("vulnerability_details.html", TEMPLATE_VULNERABILITY_DETAILS),
-            ("modal.js", JS_MODAL_HANDLER),
-            ("category-filter.js", JS_CATEGORY_FILTER),
-            ("vulnerability-expansion.js", JS_VULNERABILITY_EXPANSION),
-            ("vulnerability-details-display.js", JS_VULNERABILITY_DETAILS),
-            ("severity-filter.js", JS_SEVERITY_FILTER),
-            ("styles.css", STYLES),
-        ])?;
+        tera.add_raw_templates(TEMPLATES.to_vec())?;
+        if let Some(template_dir) = template_dir {
+            let custom = read_custom_templates(template_dir, &["html", "js", "css"])
+                .map_err(|e| tera::Error::chain("Failed to read the custom templates", e))?;
+            tera.add_raw_templates(custom)?;
+        }
         Ok(HtmlEngine { tera })
     }
 
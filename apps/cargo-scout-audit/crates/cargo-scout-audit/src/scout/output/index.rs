@@ -0,0 +1,48 @@
+use crate::cli_args::OutputFormat;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const INDEX_FILE: &str = "index.json";
+
+// Lists the files written to an `--output-dir`, so CI jobs can pick them up
+// without knowing the file names.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OutputIndex {
+    pub scout_version: String,
+    pub project: String,
+    pub date: String,
+    pub outputs: Vec<OutputEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OutputEntry {
+    pub format: String,
+    // Relative to the output directory.
+    pub path: PathBuf,
+}
+
+impl OutputIndex {
+    pub fn new(project: &str, date: &str) -> Self {
+        OutputIndex {
+            scout_version: env!("CARGO_PKG_VERSION").to_string(),
+            project: project.to_string(),
+            date: date.to_string(),
+            outputs: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, format: &OutputFormat, path: &Path, directory: &Path) {
+        self.outputs.push(OutputEntry {
+            format: format.name(),
+            path: path.strip_prefix(directory).unwrap_or(path).to_path_buf(),
+        });
+    }
+
+    pub fn save(&self, directory: &Path) -> Result<PathBuf> {
+        let path = directory.join(INDEX_FILE);
+        super::utils::write_to_file(&path, serde_json::to_string_pretty(self)?.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+}
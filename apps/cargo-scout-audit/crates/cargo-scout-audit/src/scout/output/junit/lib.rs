@@ -0,0 +1,177 @@
+use crate::{
+    scout::{
+        finding::Finding as JsonFinding,
+        output::report::{Finding, Report},
+    },
+    util::detectors_info::{LintInfo, LintStore},
+};
+use anyhow::Result;
+use std::{collections::BTreeMap, fmt::Write};
+
+// A failed testcase entry, one per finding.
+struct Failure<'a> {
+    finding: &'a Finding,
+    line: Option<u64>,
+}
+
+// Generates a JUnit XML report from a given `Report` object. Every analyzed
+// package is a testsuite with one testcase per detector, and every finding is a
+// failure of the testcase of its detector.
+pub fn generate_junit(
+    report: &Report,
+    findings: &[JsonFinding],
+    detectors_info: &LintStore,
+) -> Result<String> {
+    let mut lints = detectors_info.iter().collect::<Vec<_>>();
+    lints.sort_by(|a, b| a.id.cmp(&b.id));
+
+    // Keyed by crate name, which is what the findings carry.
+    let mut suites = report
+        .summary
+        .executed_on
+        .iter()
+        .map(|package| {
+            (
+                crate_name(&package.name),
+                (package.name.clone(), Vec::new()),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+    // Triaged findings are not failures
+    for finding in report.untriaged_findings() {
+        let json_finding = findings.get(finding.id as usize);
+        let package = json_finding.map(|x| x.package()).unwrap_or_default();
+        suites
+            .entry(crate_name(&package))
+            .or_insert_with(|| (package, Vec::new()))
+            .1
+            .push(Failure {
+                finding,
+                line: json_finding.and_then(first_line),
+            });
+    }
+
+    let tests = suites.len() * lints.len();
+    let mut failures = 0;
+    let mut body = String::new();
+    for (name, failed) in suites.values() {
+        failures += write_suite(&mut body, name, &lints, failed)?;
+    }
+
+    let mut xml = String::new();
+    writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        xml,
+        r#"<testsuites name="{}" tests="{}" failures="{}">"#,
+        escape(&format!("Scout - {}", report.name)),
+        tests,
+        failures
+    )?;
+    xml.push_str(&body);
+    writeln!(xml, "</testsuites>")?;
+    Ok(xml)
+}
+
+// Returns how many testcases failed.
+fn write_suite(
+    xml: &mut String,
+    package: &str,
+    lints: &[&LintInfo],
+    failed: &[Failure],
+) -> Result<usize> {
+    let mut failed_cases = 0;
+    let mut cases = String::new();
+    for lint in lints {
+        let failures = failed
+            .iter()
+            .filter(|x| x.finding.vulnerability_id == lint.id)
+            .collect::<Vec<_>>();
+        let classname = escape(&format!("scout.{}", package));
+        if failures.is_empty() {
+            writeln!(
+                cases,
+                r#"    <testcase name="{}" classname="{}"/>"#,
+                escape(&lint.id),
+                classname
+            )?;
+            continue;
+        }
+        failed_cases += 1;
+        writeln!(
+            cases,
+            r#"    <testcase name="{}" classname="{}">"#,
+            escape(&lint.id),
+            classname
+        )?;
+        for failure in failures {
+            writeln!(
+                cases,
+                r#"      <failure message="{}" type="{}">{}</failure>"#,
+                escape(&failure.finding.error_message),
+                escape(&lint.severity),
+                escape(&failure_text(failure, lint))
+            )?;
+        }
+        writeln!(cases, "    </testcase>")?;
+    }
+
+    writeln!(
+        xml,
+        r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+        escape(package),
+        lints.len(),
+        failed_cases
+    )?;
+    xml.push_str(&cases);
+    writeln!(xml, "  </testsuite>")?;
+    Ok(failed_cases)
+}
+
+fn failure_text(failure: &Failure, lint: &LintInfo) -> String {
+    let location = match failure.line {
+        Some(line) => format!("{}:{}", failure.finding.file_path, line),
+        None => failure.finding.file_path.clone(),
+    };
+    format!(
+        "File: {}\nSeverity: {}\nHelp: {}\n\n{}",
+        location, lint.severity, lint.help, failure.finding.code_snippet
+    )
+}
+
+fn first_line(finding: &JsonFinding) -> Option<u64> {
+    finding.span().map(|span| span.line_start as u64)
+}
+
+fn crate_name(package: &str) -> String {
+    package.replace('-', "_")
+}
+
+fn escape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => ret.push_str("&amp;"),
+            '<' => ret.push_str("&lt;"),
+            '>' => ret.push_str("&gt;"),
+            '"' => ret.push_str("&quot;"),
+            '\'' => ret.push_str("&apos;"),
+            // Not allowed in XML 1.0, e.g. the escape codes of colored messages
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => ret.push(c),
+        }
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn escapes_markup_and_control_characters() {
+        assert_eq!(
+            escape("a < b && \"c\"\u{1b}[0m\n"),
+            "a &lt; b &amp;&amp; &quot;c&quot;[0m\n"
+        );
+    }
+}
@@ -1,10 +1,15 @@
 use super::{generator::generate_summary_context, tera::MdEngine};
 use crate::scout::output::{report::Report, table::register_functions_for_tera_md};
 use anyhow::{Context, Result};
+use std::path::Path;
 
 // Generates an Markdown report from a given `Report` object.
-pub fn generate_markdown(report: &Report, render_styles: bool) -> Result<String> {
-    let mut tera = MdEngine::new()?;
+pub fn generate_markdown(
+    report: &Report,
+    render_styles: bool,
+    template_dir: Option<&Path>,
+) -> Result<String> {
+    let mut tera = MdEngine::new(template_dir)?;
 
     let (summary, table) = generate_summary_context(report);
 
@@ -1,6 +1,10 @@
+use crate::scout::output::utils::read_custom_templates;
+use std::path::Path;
 use tera::{Context, Result, Tera};
 
-const TEMPLATE_STR: &str = include_str!("./template.md");
+pub const TEMPLATE_STR: &str = include_str!("./template.md");
+// Name of the main template in a custom template directory.
+pub const TEMPLATE_FILE: &str = "report.md";
 
 fn get_template_path() -> (String, String) {
     (
@@ -15,9 +19,20 @@ pub struct MdEngine {
 }
 
 impl MdEngine {
-    pub fn new() -> Result<Self> {
+    // With `template_dir`, its `report.md` replaces the built-in template, and
+    // the other `.md` files can be included from it.
+    pub fn new(template_dir: Option<&Path>) -> Result<Self> {
         let mut tera = Tera::default();
-        let template = crate::scout::output::utils::get_template(get_template_path, TEMPLATE_STR);
+        let mut custom = match template_dir {
+            Some(template_dir) => read_custom_templates(template_dir, &["md"])
+                .map_err(|e| tera::Error::chain("Failed to read the custom templates", e))?,
+            None => Vec::new(),
+        };
+        let template = match custom.iter().position(|(name, _)| name == TEMPLATE_FILE) {
+            Some(index) => custom.remove(index).1,
+            None => crate::scout::output::utils::get_template(get_template_path, TEMPLATE_STR),
+        };
+        tera.add_raw_templates(custom)?;
         tera.add_raw_template("base_template", template.as_str())?;
         Ok(MdEngine { tera })
     }
@@ -44,3 +59,28 @@ impl MdEngine {
         &mut self.tera
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MdEngine, TEMPLATE_FILE};
+    use std::fs;
+
+    #[test]
+    fn custom_template_replaces_the_built_in_one() {
+        let directory = tempfile::TempDir::new().unwrap();
+        fs::create_dir(directory.path().join("partials")).unwrap();
+        fs::write(
+            directory.path().join(TEMPLATE_FILE),
+            "# {{ name }}\n{% include \"partials/footer.md\" %}",
+        )
+        .unwrap();
+        fs::write(directory.path().join("partials/footer.md"), "Acme Audits").unwrap();
+
+        let engine = MdEngine::new(Some(directory.path())).unwrap();
+        let context = engine.create_context("name", "Token");
+        assert_eq!(
+            engine.render_template(vec![context]).unwrap(),
+            "# Token\nAcme Audits"
+        );
+    }
+}
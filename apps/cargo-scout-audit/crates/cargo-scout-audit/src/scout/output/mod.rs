@@ -1,8 +1,17 @@
 pub mod console;
+pub mod events;
+pub mod gitlab;
 pub mod html;
+pub mod index;
+pub mod junit;
 pub mod markdown;
 pub mod pdf;
 pub mod raw_report;
 pub mod report;
+pub mod report_diff;
+pub mod sarif;
+pub mod sonar;
 pub mod table;
+pub mod template;
+pub mod tui;
 pub mod utils;
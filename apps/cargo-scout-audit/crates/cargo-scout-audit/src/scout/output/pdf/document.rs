@@ -0,0 +1,385 @@
+use anyhow::Result;
+use flate2::{Compression, write::ZlibEncoder};
+use std::io::Write;
+
+// A4, in points
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+// Widths of the printable ASCII characters (32 to 126) in the standard
+// Helvetica fonts, in thousandths of the font size.
+const HELVETICA_WIDTHS: [u16; 95] = [
+    278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, 1015, 667, 667, 722, 722, 667,
+    611, 778, 722, 278, 500, 667, 556, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 278, 278, 278, 469, 556, 333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500,
+    222, 833, 556, 556, 556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584,
+];
+
+const HELVETICA_BOLD_WIDTHS: [u16; 95] = [
+    278, 333, 474, 556, 556, 889, 722, 238, 333, 333, 389, 584, 278, 333, 278, 278, 556, 556, 556,
+    556, 556, 556, 556, 556, 556, 556, 333, 333, 584, 584, 584, 611, 975, 722, 722, 722, 722, 667,
+    611, 778, 722, 278, 556, 722, 611, 833, 722, 778, 667, 778, 722, 667, 611, 722, 667, 944, 667,
+    667, 611, 333, 278, 333, 584, 556, 333, 556, 611, 556, 611, 556, 333, 611, 611, 278, 278, 556,
+    278, 889, 611, 611, 611, 611, 389, 556, 333, 611, 556, 778, 556, 556, 500, 389, 280, 389, 584,
+];
+
+// The standard 14 fonts are available in every PDF reader, so nothing has to
+// be embedded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Font {
+    Regular,
+    Bold,
+    Mono,
+}
+
+impl Font {
+    const ALL: [Font; 3] = [Font::Regular, Font::Bold, Font::Mono];
+
+    fn resource_name(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+            Font::Mono => "F3",
+        }
+    }
+
+    fn base_font(&self) -> &'static str {
+        match self {
+            Font::Regular => "Helvetica",
+            Font::Bold => "Helvetica-Bold",
+            Font::Mono => "Courier",
+        }
+    }
+
+    fn char_width(&self, c: char) -> u16 {
+        let widths = match self {
+            Font::Regular => &HELVETICA_WIDTHS,
+            Font::Bold => &HELVETICA_BOLD_WIDTHS,
+            Font::Mono => return 600,
+        };
+        match encode_char(c) {
+            byte @ 32..=126 => widths[(byte - 32) as usize],
+            _ => 556,
+        }
+    }
+
+    pub fn width(&self, text: &str, size: f32) -> f32 {
+        let total: u32 = text.chars().map(|c| u32::from(self.char_width(c))).sum();
+        total as f32 * size / 1000.0
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Rgb(pub f32, pub f32, pub f32);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0.0, 0.0, 0.0);
+}
+
+// A minimal PDF writer. Coordinates are in points from the bottom left corner
+// of the page, as in PDF itself.
+#[derive(Default)]
+pub struct Document {
+    pages: Vec<Vec<u8>>,
+}
+
+impl Document {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_page(&mut self) {
+        self.pages.push(Vec::new());
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    // Drawing starts a page if there is none yet.
+    fn content(&mut self, page: usize) -> &mut Vec<u8> {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+        let page = page.min(self.pages.len() - 1);
+        &mut self.pages[page]
+    }
+
+    fn last_page(&self) -> usize {
+        self.pages.len().saturating_sub(1)
+    }
+
+    pub fn text(&mut self, x: f32, y: f32, font: Font, size: f32, color: Rgb, text: &str) {
+        self.text_on(self.last_page(), x, y, font, size, color, text);
+    }
+
+    // Like `text`, but on a page other than the last one, e.g. for footers
+    // written once the page count is known.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_on(
+        &mut self,
+        page: usize,
+        x: f32,
+        y: f32,
+        font: Font,
+        size: f32,
+        color: Rgb,
+        text: &str,
+    ) {
+        let content = self.content(page);
+        content.extend_from_slice(
+            format!(
+                "BT {} {} {} rg /{} {} Tf {} {} Td ",
+                number(color.0),
+                number(color.1),
+                number(color.2),
+                font.resource_name(),
+                number(size),
+                number(x),
+                number(y)
+            )
+            .as_bytes(),
+        );
+        content.extend_from_slice(&string_literal(text));
+        content.extend_from_slice(b" Tj ET\n");
+    }
+
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        let content = self.content(self.last_page());
+        content.extend_from_slice(
+            format!(
+                "{} g {} {} {} {} re f\n",
+                number(gray),
+                number(x),
+                number(y),
+                number(width),
+                number(height)
+            )
+            .as_bytes(),
+        );
+    }
+
+    pub fn stroke_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        let content = self.content(self.last_page());
+        content.extend_from_slice(
+            format!(
+                "{} G 0.5 w {} {} {} {} re S\n",
+                number(gray),
+                number(x),
+                number(y),
+                number(width),
+                number(height)
+            )
+            .as_bytes(),
+        );
+    }
+
+    pub fn to_bytes(&self, title: &str) -> Result<Vec<u8>> {
+        let mut writer = ObjectWriter::new();
+
+        // 1: catalog, 2: page tree, 3: info, then the fonts and, for every
+        // page, the page object followed by its content stream.
+        let first_font = 4;
+        let first_page = first_font + Font::ALL.len();
+        let page_ids = (0..self.pages.len().max(1))
+            .map(|i| first_page + 2 * i)
+            .collect::<Vec<_>>();
+
+        writer.object(1, b"<< /Type /Catalog /Pages 2 0 R >>");
+        let kids = page_ids
+            .iter()
+            .map(|id| format!("{} 0 R", id))
+            .collect::<Vec<_>>()
+            .join(" ");
+        writer.object(
+            2,
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids,
+                page_ids.len()
+            )
+            .as_bytes(),
+        );
+
+        let mut info = b"<< /Title ".to_vec();
+        info.extend_from_slice(&string_literal(title));
+        info.extend_from_slice(b" /Producer ");
+        info.extend_from_slice(&string_literal(&format!(
+            "Scout {}",
+            env!("CARGO_PKG_VERSION")
+        )));
+        info.extend_from_slice(b" >>");
+        writer.object(3, &info);
+
+        for (i, font) in Font::ALL.iter().enumerate() {
+            writer.object(
+                first_font + i,
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font.base_font()
+                )
+                .as_bytes(),
+            );
+        }
+
+        let fonts = Font::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, font)| format!("/{} {} 0 R", font.resource_name(), first_font + i))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let empty = Vec::new();
+        for (i, id) in page_ids.iter().enumerate() {
+            writer.object(
+                *id,
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                     /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+                    number(PAGE_WIDTH),
+                    number(PAGE_HEIGHT),
+                    fonts,
+                    id + 1
+                )
+                .as_bytes(),
+            );
+
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(self.pages.get(i).unwrap_or(&empty))?;
+            let stream = encoder.finish()?;
+            let mut object = format!(
+                "<< /Length {} /Filter /FlateDecode >>\nstream\n",
+                stream.len()
+            )
+            .into_bytes();
+            object.extend_from_slice(&stream);
+            object.extend_from_slice(b"\nendstream");
+            writer.object(id + 1, &object);
+        }
+
+        Ok(writer.finish(1, 3))
+    }
+}
+
+// Writes the objects in order and keeps their offsets for the cross-reference
+// table.
+struct ObjectWriter {
+    bytes: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl ObjectWriter {
+    fn new() -> Self {
+        Self {
+            // The binary comment tells transfer tools the file is not text
+            bytes: b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec(),
+            offsets: Vec::new(),
+        }
+    }
+
+    // Objects must be written with consecutive ids, starting at 1.
+    fn object(&mut self, id: usize, body: &[u8]) {
+        debug_assert_eq!(id, self.offsets.len() + 1);
+        self.offsets.push(self.bytes.len());
+        self.bytes
+            .extend_from_slice(format!("{} 0 obj\n", id).as_bytes());
+        self.bytes.extend_from_slice(body);
+        self.bytes.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn finish(mut self, root: usize, info: usize) -> Vec<u8> {
+        let xref = self.bytes.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            table.push_str(&format!("{:010} 00000 n \n", offset));
+        }
+        table.push_str(&format!(
+            "trailer\n<< /Size {} /Root {} 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            root,
+            info,
+            xref
+        ));
+        self.bytes.extend_from_slice(table.as_bytes());
+        self.bytes
+    }
+}
+
+fn number(x: f32) -> String {
+    let s = format!("{:.2}", x);
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+// Maps a character to WinAnsiEncoding, which matches Latin-1 for the
+// characters below 256. Anything else is replaced.
+fn encode_char(c: char) -> u8 {
+    match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u8,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        _ => b'?',
+    }
+}
+
+fn string_literal(text: &str) -> Vec<u8> {
+    let mut ret = vec![b'('];
+    for c in text.chars() {
+        match encode_char(c) {
+            byte @ (b'(' | b')' | b'\\') => ret.extend_from_slice(&[b'\\', byte]),
+            byte => ret.push(byte),
+        }
+    }
+    ret.push(b')');
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Font, Rgb, string_literal};
+
+    #[test]
+    fn cross_references_point_to_objects() {
+        let mut document = Document::new();
+        document.add_page();
+        document.text(50.0, 800.0, Font::Regular, 12.0, Rgb::BLACK, "Page (1)");
+        document.add_page();
+        document.text(50.0, 800.0, Font::Mono, 10.0, Rgb::BLACK, "fn main() {}");
+        let bytes = document.to_bytes("Scout Report").unwrap();
+
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(bytes.starts_with(b"%PDF-1.4"));
+        assert!(text.contains("/Count 2"));
+
+        let startxref = text.rfind("startxref\n").unwrap() + "startxref\n".len();
+        let xref = text[startxref..].lines().next().unwrap();
+        let xref = xref.parse::<usize>().unwrap();
+        assert!(bytes[xref..].starts_with(b"xref\n"));
+
+        let entries = text[xref..].lines().skip(3);
+        for (i, entry) in entries.take_while(|x| x.ends_with(" n ")).enumerate() {
+            let offset = entry[..10].parse::<usize>().unwrap();
+            let header = format!("{} 0 obj", i + 1);
+            assert!(bytes[offset..].starts_with(header.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn escapes_and_encodes_strings() {
+        assert_eq!(
+            string_literal("a(b)\\ é ✓"),
+            b"(a\\(b\\)\\\\ \xe9 ?)".to_vec()
+        );
+        assert_eq!(Font::Mono.width("abcd", 10.0), 24.0);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::util::build_and_run::PackageToBuild;
+use crate::{
+    bundle::DetectorBundle,
+    util::{build_and_run::PackageToBuild, print::print_warning},
+};
 use anyhow::{Context, Result};
 use libloading::Symbol;
 use std::{os::raw::c_uchar, path::PathBuf, sync::Arc};
@@ -6,11 +9,26 @@ use std::{os::raw::c_uchar, path::PathBuf, sync::Arc};
 const URL: &str = "https://github.com/CoinFabrik/html-to-pdf";
 const BRANCH: &str = "master";
 
-pub fn build_library() -> Result<PathBuf> {
+// Offline runs use the generator installed from a bundle. Online runs build the
+// current one, and only fall back to the bundled one if that fails.
+pub fn build_library(offline: bool) -> Result<PathBuf> {
+    let installed = DetectorBundle::installed_pdf_generator();
+    if offline {
+        return installed.context("The installed bundle has no PDF generator");
+    }
+
     let mut pkg = PackageToBuild::new_remote(URL, BRANCH, "library");
     pkg.build_message = "Building PDF generator. Please wait.".to_string();
     pkg.build_error_message = "Failed to build PDF generator".to_string();
-    pkg.build_library(None)
+    match (pkg.build_library(None), installed) {
+        (Err(e), Some(path)) => {
+            print_warning(&format!(
+                "{e:#}. Using the PDF generator of the installed bundle instead."
+            ));
+            Ok(path)
+        }
+        (result, _) => result,
+    }
 }
 
 fn to_vec(s: &str) -> Vec<u8> {
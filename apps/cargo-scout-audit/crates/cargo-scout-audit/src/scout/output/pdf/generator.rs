@@ -1,7 +1,10 @@
-use crate::scout::output::{
-    report::{Category, Finding, Report},
-    table::prepare_tera_for_table_render_html,
-    utils,
+use crate::scout::{
+    core::{baseline::BaselineFinding, suppression::SuppressedFinding},
+    output::{
+        report::{Category, Finding, Report},
+        table::prepare_tera_for_table_render_html,
+        utils,
+    },
 };
 use std::collections::HashMap;
 use tera::{Context, Tera};
@@ -73,7 +76,7 @@ pub fn generate_summary(report: &Report) -> String {
 }
 
 // This function summarizes the findings by category
-fn summarize_findings(
+pub(super) fn summarize_findings(
     categories: &[Category],
     findings: &[Finding],
 ) -> HashMap<String, (usize, String)> {
@@ -131,9 +134,10 @@ fn generate_category(category: &Category) -> String {
 // Function to generate a table for a category
 fn generate_table_for_category(category: &Category, findings: &[Finding]) -> String {
     let table_header = "<table style=\"width: 100%; table-layout: fixed;\">\n<thead>\n<tr>\
-                        <th style=\"width: 20%;\">ID</th>\
-                        <th style=\"width: 30%;\">Package</th>\
-                        <th style=\"width: 50%;\">Detection</th>\
+                        <th style=\"width: 15%;\">ID</th>\
+                        <th style=\"width: 25%;\">Package</th>\
+                        <th style=\"width: 40%;\">Detection</th>\
+                        <th style=\"width: 20%;\">Triage</th>\
                         </tr>\n</thead>\n<tbody>\n";
     let table_body: String = findings
         .iter()
@@ -146,7 +150,65 @@ fn generate_table_for_category(category: &Category, findings: &[Finding]) -> Str
 // Function to generate HTML for a finding
 fn generate_finding(finding: &Finding) -> String {
     format!(
-        "<tr>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n</tr>\n",
-        finding.id, finding.package, finding.span
+        "<tr>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n</tr>\n",
+        finding.id,
+        finding.package,
+        finding.span,
+        finding
+            .triage
+            .as_ref()
+            .map(|triage| triage.status.to_string())
+            .unwrap_or_default()
     )
 }
+
+// Generate the list of baseline findings that no longer appear
+pub fn generate_fixed_since_baseline(fixed: &[BaselineFinding]) -> String {
+    if fixed.is_empty() {
+        return String::new();
+    }
+    let table_header = "<h2>Fixed since baseline</h2>\n\
+                        <table style=\"width: 100%; table-layout: fixed;\">\n<thead>\n<tr>\
+                        <th style=\"width: 30%;\">Detector</th>\
+                        <th style=\"width: 20%;\">Package</th>\
+                        <th style=\"width: 50%;\">File</th>\
+                        </tr>\n</thead>\n<tbody>\n";
+    let table_body: String = fixed
+        .iter()
+        .map(|finding| {
+            format!(
+                "<tr>\n<td>{}</td>\n<td>{}</td>\n<td>{}</td>\n</tr>\n",
+                finding.vulnerability_id, finding.package, finding.file_path
+            )
+        })
+        .collect();
+    format!("{}{}</tbody>\n</table>\n", table_header, table_body)
+}
+
+// Generate the list of findings silenced with `scout_allow`
+pub fn generate_suppressed(suppressed: &[SuppressedFinding]) -> String {
+    if suppressed.is_empty() {
+        return String::new();
+    }
+    let table_header = "<h2>Suppressed findings</h2>\n\
+                        <table style=\"width: 100%; table-layout: fixed;\">\n<thead>\n<tr>\
+                        <th style=\"width: 25%;\">Detector</th>\
+                        <th style=\"width: 35%;\">Location</th>\
+                        <th style=\"width: 40%;\">Reason</th>\
+                        </tr>\n</thead>\n<tbody>\n";
+    let table_body: String = suppressed
+        .iter()
+        .map(|finding| {
+            let reason = if finding.reason.is_empty() {
+                "No reason given"
+            } else {
+                &finding.reason
+            };
+            format!(
+                "<tr>\n<td>{}</td>\n<td>{}:{}</td>\n<td>{}</td>\n</tr>\n",
+                finding.vulnerability_id, finding.file_path, finding.line, reason
+            )
+        })
+        .collect();
+    format!("{}{}</tbody>\n</table>\n", table_header, table_body)
+}
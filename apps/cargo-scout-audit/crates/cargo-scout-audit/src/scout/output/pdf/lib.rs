@@ -1,4 +1,7 @@
-use super::generator::{generate_body, generate_header, generate_summary};
+use super::generator::{
+    generate_body, generate_fixed_since_baseline, generate_header, generate_summary,
+    generate_suppressed,
+};
 use crate::scout::output::{
     pdf::external::{build_library, call},
     report::Report,
@@ -21,6 +24,12 @@ fn generate_temp_html(report: &Report) -> Result<NamedTempFile> {
     // Body
     report_html.push_str(&generate_body(&report.categories, &report.findings));
 
+    // Suppressed findings
+    report_html.push_str(&generate_suppressed(&report.suppressed_findings));
+
+    // Baseline
+    report_html.push_str(&generate_fixed_since_baseline(&report.fixed_since_baseline));
+
     let mut file = Builder::new()
         .suffix(".html")
         .tempfile()
@@ -30,9 +39,29 @@ fn generate_temp_html(report: &Report) -> Result<NamedTempFile> {
     Ok(file)
 }
 
-pub fn generate_pdf(path: &Path, report: &Report) -> Result<()> {
+// Builds with the `native_pdf` feature render the report themselves, and
+// only fall back to the external html-to-pdf library if that fails. Without
+// the feature, the external library always converts the HTML report.
+pub fn generate_pdf(path: &Path, report: &Report, offline: bool) -> Result<()> {
+    #[cfg(feature = "native_pdf")]
+    {
+        super::native::generate_pdf(path, report).or_else(|native_error| {
+            generate_external_pdf(path, report, offline).map_err(|external_error| {
+                native_error.context(format!(
+                    "The external PDF generator also failed: {external_error:#}"
+                ))
+            })
+        })
+    }
+    #[cfg(not(feature = "native_pdf"))]
+    {
+        generate_external_pdf(path, report, offline)
+    }
+}
+
+fn generate_external_pdf(path: &Path, report: &Report, offline: bool) -> Result<()> {
     let temp_html = generate_temp_html(report)?;
-    let library = build_library()?;
+    let library = build_library(offline)?;
     let url = "file:///".to_string() + temp_html.path().to_str().unwrap();
     let output_path = path
         .as_os_str()
@@ -1,4 +1,8 @@
 mod generator;
 pub mod lib;
 pub use lib::generate_pdf;
-mod external;
+#[cfg(feature = "native_pdf")]
+mod document;
+pub mod external;
+#[cfg(feature = "native_pdf")]
+mod native;
@@ -0,0 +1,423 @@
+use super::{
+    document::{Document, Font, PAGE_HEIGHT, PAGE_WIDTH, Rgb},
+    generator::summarize_findings,
+};
+use crate::scout::{
+    core::triage::TriageEntry,
+    output::{
+        report::{Category, Finding, Report},
+        utils,
+    },
+};
+use anyhow::{Context, Result};
+use std::{fs, path::Path};
+
+const MARGIN: f32 = 50.0;
+const CONTENT_WIDTH: f32 = PAGE_WIDTH - 2.0 * MARGIN;
+const TEXT_SIZE: f32 = 10.0;
+const CODE_SIZE: f32 = 8.0;
+const CELL_PADDING: f32 = 4.0;
+
+const TEXT_COLOR: Rgb = Rgb(0.2, 0.2, 0.2);
+const MUTED_COLOR: Rgb = Rgb(0.45, 0.45, 0.45);
+
+// Renders the report straight to PDF, with the same sections as the HTML
+// used by the external generator plus the code snippet of every finding.
+pub fn generate_pdf(path: &Path, report: &Report) -> Result<()> {
+    let mut layout = Layout::new();
+    let title = format!("Scout Report - {}", report.date);
+
+    layout.heading(&title, 20.0);
+    layout.paragraph(&report.name, Font::Regular, TEXT_SIZE, MUTED_COLOR);
+
+    // Summary
+    layout.heading("Summary", 16.0);
+    let table = &report.summary.table;
+    let header = table
+        .header()
+        .iter()
+        .map(|cell| cell.content.clone())
+        .collect::<Vec<_>>();
+    let rows = table
+        .rows()
+        .map(|row| row.iter().map(|cell| cell.content.clone()).collect())
+        .collect::<Vec<_>>();
+    let widths = vec![1.0 / header.len().max(1) as f32; header.len()];
+    layout.table(&widths, &header, &rows);
+
+    layout.heading("Issues found:", 12.0);
+    let findings_summary = summarize_findings(&report.categories, &report.findings);
+    for category in &report.categories {
+        if let Some((count, severity)) = findings_summary.get(&category.id) {
+            layout.paragraph(
+                &format!(
+                    "\u{2022} {} ({} results) ({})",
+                    category.name, count, severity
+                ),
+                Font::Regular,
+                TEXT_SIZE,
+                TEXT_COLOR,
+            );
+        }
+    }
+
+    // Body
+    for category in &report.categories {
+        generate_category(&mut layout, category, &report.findings);
+    }
+
+    // Suppressed findings
+    if !report.suppressed_findings.is_empty() {
+        layout.heading("Suppressed findings", 16.0);
+        let rows = report
+            .suppressed_findings
+            .iter()
+            .map(|finding| {
+                let reason = if finding.reason.is_empty() {
+                    "No reason given"
+                } else {
+                    &finding.reason
+                };
+                vec![
+                    finding.vulnerability_id.clone(),
+                    format!("{}:{}", finding.file_path, finding.line),
+                    reason.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        layout.table(
+            &[0.25, 0.35, 0.4],
+            &["Detector".into(), "Location".into(), "Reason".into()],
+            &rows,
+        );
+    }
+
+    // Baseline
+    if !report.fixed_since_baseline.is_empty() {
+        layout.heading("Fixed since baseline", 16.0);
+        let rows = report
+            .fixed_since_baseline
+            .iter()
+            .map(|finding| {
+                vec![
+                    finding.vulnerability_id.clone(),
+                    finding.package.clone(),
+                    finding.file_path.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        layout.table(
+            &[0.3, 0.2, 0.5],
+            &["Detector".into(), "Package".into(), "File".into()],
+            &rows,
+        );
+    }
+
+    // Diff base
+    if !report.pre_existing_findings.is_empty() {
+        layout.heading("Pre-existing findings", 16.0);
+        let rows = report
+            .pre_existing_findings
+            .iter()
+            .map(|finding| {
+                vec![
+                    finding.vulnerability_id.clone(),
+                    format!("{}:{}", finding.file_path, finding.line),
+                    finding.error_message.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        layout.table(
+            &[0.25, 0.35, 0.4],
+            &["Detector".into(), "Location".into(), "Message".into()],
+            &rows,
+        );
+    }
+
+    let bytes = layout.finish(&title)?;
+    fs::write(path, bytes).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn generate_category(layout: &mut Layout, category: &Category, findings: &[Finding]) {
+    layout.heading(&category.name, 16.0);
+    for vulnerability in &category.vulnerabilities {
+        layout.heading(&vulnerability.name, 12.0);
+        layout.field("Impact:", &utils::capitalize(&vulnerability.severity));
+        layout.field("Description:", &vulnerability.short_message);
+        layout.field("More about:", &vulnerability.help);
+    }
+
+    for finding in findings.iter().filter(|x| x.category_id == category.id) {
+        let mut title = format!("Finding {} - {}", finding.id, finding.package);
+        if finding.from_cache {
+            title.push_str(" (cached)");
+        }
+        layout.space(6.0);
+        layout.keep_together(3.0 * line_height(TEXT_SIZE) + line_height(CODE_SIZE));
+        layout.paragraph(&title, Font::Bold, TEXT_SIZE, TEXT_COLOR);
+        layout.field("Location:", &finding.span);
+        layout.field("Message:", &finding.error_message);
+        layout.field("Fingerprint:", &finding.fingerprint);
+        if let Some(triage) = &finding.triage {
+            layout.field("Triage:", &triage_text(triage));
+        }
+        if !finding.code_snippet.is_empty() {
+            layout.code(&finding.code_snippet);
+        }
+    }
+}
+
+fn triage_text(triage: &TriageEntry) -> String {
+    let mut text = format!("{} by {} on {}", triage.status, triage.author, triage.date);
+    if !triage.comment.is_empty() {
+        text.push_str(": ");
+        text.push_str(&triage.comment);
+    }
+    text
+}
+
+fn line_height(size: f32) -> f32 {
+    size * 1.35
+}
+
+// Flows content down the pages, starting a new one when it runs out of
+// space. `y` is measured from the top of the page.
+struct Layout {
+    document: Document,
+    y: f32,
+}
+
+impl Layout {
+    fn new() -> Self {
+        let mut document = Document::new();
+        document.add_page();
+        Self {
+            document,
+            y: MARGIN,
+        }
+    }
+
+    fn keep_together(&mut self, height: f32) {
+        if self.y + height > PAGE_HEIGHT - MARGIN {
+            self.document.add_page();
+            self.y = MARGIN;
+        }
+    }
+
+    fn space(&mut self, height: f32) {
+        self.y += height;
+    }
+
+    // Draws a line of text whose top is at the current position.
+    fn text_line(&mut self, x: f32, font: Font, size: f32, color: Rgb, text: &str) {
+        let baseline = PAGE_HEIGHT - self.y - size;
+        self.document.text(x, baseline, font, size, color, text);
+    }
+
+    fn heading(&mut self, text: &str, size: f32) {
+        self.space(size * 0.6);
+        // A heading is never left alone at the bottom of a page
+        self.keep_together(line_height(size) + 3.0 * line_height(TEXT_SIZE));
+        for line in wrap(text, Font::Bold, size, CONTENT_WIDTH) {
+            self.text_line(MARGIN, Font::Bold, size, TEXT_COLOR, &line);
+            self.y += line_height(size);
+        }
+        self.space(size * 0.2);
+    }
+
+    fn paragraph(&mut self, text: &str, font: Font, size: f32, color: Rgb) {
+        for line in wrap(text, font, size, CONTENT_WIDTH) {
+            self.keep_together(line_height(size));
+            self.text_line(MARGIN, font, size, color, &line);
+            self.y += line_height(size);
+        }
+    }
+
+    // A bold label followed by its value, which wraps with a hanging indent.
+    fn field(&mut self, label: &str, value: &str) {
+        let indent = Font::Bold.width(label, TEXT_SIZE) + 4.0;
+        let lines = wrap(value, Font::Regular, TEXT_SIZE, CONTENT_WIDTH - indent);
+        for (i, line) in lines.iter().enumerate() {
+            self.keep_together(line_height(TEXT_SIZE));
+            if i == 0 {
+                self.text_line(MARGIN, Font::Bold, TEXT_SIZE, TEXT_COLOR, label);
+            }
+            self.text_line(MARGIN + indent, Font::Regular, TEXT_SIZE, TEXT_COLOR, line);
+            self.y += line_height(TEXT_SIZE);
+        }
+    }
+
+    // Columns are given as fractions of the content width. The header is
+    // repeated on every page the table spans.
+    fn table(&mut self, widths: &[f32], header: &[String], rows: &[Vec<String>]) {
+        let widths = widths.iter().map(|x| x * CONTENT_WIDTH).collect::<Vec<_>>();
+        self.keep_together(2.0 * (line_height(TEXT_SIZE) + 2.0 * CELL_PADDING));
+        self.table_row(&widths, header, Font::Bold, true);
+        for row in rows {
+            let height = self.row_height(&widths, row, Font::Regular);
+            if self.y + height > PAGE_HEIGHT - MARGIN {
+                self.document.add_page();
+                self.y = MARGIN;
+                self.table_row(&widths, header, Font::Bold, true);
+            }
+            self.table_row(&widths, row, Font::Regular, false);
+        }
+        self.space(line_height(TEXT_SIZE));
+    }
+
+    fn row_height(&self, widths: &[f32], cells: &[String], font: Font) -> f32 {
+        let lines = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| wrap(cell, font, TEXT_SIZE, width - 2.0 * CELL_PADDING).len())
+            .max()
+            .unwrap_or(1);
+        lines as f32 * line_height(TEXT_SIZE) + 2.0 * CELL_PADDING
+    }
+
+    fn table_row(&mut self, widths: &[f32], cells: &[String], font: Font, shaded: bool) {
+        let height = self.row_height(widths, cells, font);
+        let bottom = PAGE_HEIGHT - self.y - height;
+        let mut x = MARGIN;
+        for (cell, width) in cells.iter().zip(widths) {
+            if shaded {
+                self.document.fill_rect(x, bottom, *width, height, 0.94);
+            }
+            self.document.stroke_rect(x, bottom, *width, height, 0.8);
+            let top = self.y;
+            self.y += CELL_PADDING;
+            for line in wrap(cell, font, TEXT_SIZE, width - 2.0 * CELL_PADDING) {
+                self.text_line(x + CELL_PADDING, font, TEXT_SIZE, TEXT_COLOR, &line);
+                self.y += line_height(TEXT_SIZE);
+            }
+            self.y = top;
+            x += width;
+        }
+        self.y += height;
+    }
+
+    // Code goes on a shaded block, split across pages if needed.
+    fn code(&mut self, snippet: &str) {
+        let lines = wrap_code(snippet, CODE_SIZE, CONTENT_WIDTH - 2.0 * CELL_PADDING);
+        let mut remaining = lines.as_slice();
+        self.space(2.0);
+        while !remaining.is_empty() {
+            self.keep_together(line_height(CODE_SIZE) + 2.0 * CELL_PADDING);
+            let available = PAGE_HEIGHT - MARGIN - self.y - 2.0 * CELL_PADDING;
+            let count = ((available / line_height(CODE_SIZE)) as usize).clamp(1, remaining.len());
+            let (chunk, rest) = remaining.split_at(count);
+            remaining = rest;
+
+            let height = chunk.len() as f32 * line_height(CODE_SIZE) + 2.0 * CELL_PADDING;
+            self.document.fill_rect(
+                MARGIN,
+                PAGE_HEIGHT - self.y - height,
+                CONTENT_WIDTH,
+                height,
+                0.96,
+            );
+            self.y += CELL_PADDING;
+            for line in chunk {
+                self.text_line(
+                    MARGIN + CELL_PADDING,
+                    Font::Mono,
+                    CODE_SIZE,
+                    Rgb::BLACK,
+                    line,
+                );
+                self.y += line_height(CODE_SIZE);
+            }
+            self.y += CELL_PADDING;
+        }
+        self.space(4.0);
+    }
+
+    fn finish(mut self, title: &str) -> Result<Vec<u8>> {
+        let pages = self.document.page_count();
+        for page in 0..pages {
+            let footer = format!("Page {} of {}", page + 1, pages);
+            let x = PAGE_WIDTH - MARGIN - Font::Regular.width(&footer, 8.0);
+            self.document.text_on(
+                page,
+                x,
+                MARGIN / 2.0,
+                Font::Regular,
+                8.0,
+                MUTED_COLOR,
+                &footer,
+            );
+        }
+        self.document.to_bytes(title)
+    }
+}
+
+// Greedy word wrapping. Words wider than a line are split wherever they
+// overflow.
+fn wrap(text: &str, font: Font, size: f32, width: f32) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let candidate = if line.is_empty() {
+                word.to_string()
+            } else {
+                format!("{} {}", line, word)
+            };
+            if font.width(&candidate, size) <= width {
+                line = candidate;
+                continue;
+            }
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            for c in word.chars() {
+                if !line.is_empty() && font.width(&format!("{}{}", line, c), size) > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(c);
+            }
+        }
+        lines.push(line);
+    }
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+// Code keeps its indentation and is broken at a fixed number of characters.
+fn wrap_code(snippet: &str, size: f32, width: f32) -> Vec<String> {
+    let columns = ((width / Font::Mono.width(" ", size)) as usize).max(1);
+    snippet
+        .replace('\t', "    ")
+        .lines()
+        .flat_map(|line| {
+            let chars = line.trim_end().chars().collect::<Vec<_>>();
+            if chars.is_empty() {
+                return vec![String::new()];
+            }
+            chars
+                .chunks(columns)
+                .map(|chunk| chunk.iter().collect())
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Font, wrap, wrap_code};
+
+    #[test]
+    fn wraps_words_and_long_tokens() {
+        let width = Font::Regular.width("aaaa bbbb", 10.0);
+        assert_eq!(
+            wrap("aaaa bbbb cccc\n\ndddddddddddd", Font::Regular, 10.0, width),
+            ["aaaa bbbb", "cccc", "", "dddddddd", "dddd"]
+        );
+        assert_eq!(
+            wrap_code("\tlet x = 1;\n", 10.0, 60.0),
+            ["    let x ", "= 1;"]
+        );
+    }
+}
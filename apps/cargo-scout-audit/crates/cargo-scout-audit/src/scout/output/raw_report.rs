@@ -1,10 +1,9 @@
 use super::report::{Category, Finding, Report, Severity, Summary, Vulnerability};
 use crate::{
     scout::{core::project_info::Project, finding::Finding as JsonFinding},
-    util::{detectors_info::LintStore, json::json_to_string},
+    util::detectors_info::LintStore,
 };
 use anyhow::{Context, Result};
-use serde_json::Value;
 use std::{
     collections::HashMap,
     io::{BufReader, Read, Seek, SeekFrom},
@@ -80,6 +79,7 @@ fn process_findings(
         })?;
 
         let error_message = parse_error_message(finding);
+        let fingerprint = finding.fingerprint().to_string();
 
         let occurrence_index = det_map.entry(code.clone()).or_insert(0);
         *occurrence_index += 1;
@@ -98,6 +98,9 @@ fn process_findings(
             code_snippet,
             package,
             file_path: relative_path,
+            fingerprint,
+            from_cache: finding.from_cache(),
+            triage: None,
         });
     }
 
@@ -114,15 +117,9 @@ fn get_code(finding: &JsonFinding) -> Result<String> {
 }
 
 fn parse_file_details(finding: &JsonFinding, workspace_root: &Path) -> Result<FileDetails> {
-    let spans = finding
-        .spans()
+    let relative_path = finding
+        .file_name()
         .with_context(|| "File name not found in finding structure")?;
-    let relative_path = json_to_string(
-        spans
-            .get(0)
-            .and_then(|span| span.get("file_name"))
-            .with_context(|| "File name not found in finding structure")?,
-    );
 
     let absolute_path = workspace_root.join(&relative_path);
 
@@ -143,43 +140,22 @@ fn parse_file_details(finding: &JsonFinding, workspace_root: &Path) -> Result<Fi
 
 fn parse_span(finding: &JsonFinding, file_name: &str) -> String {
     finding
-        .spans()
-        .map(|spans| match spans {
-            Value::Array(spans) => {
-                if spans.is_empty() {
-                    None
-                } else {
-                    let span = &spans[0];
-                    Some(format!(
-                        "{}:{}:{} - {}:{}",
-                        file_name,
-                        span.get("line_start").unwrap_or(&Value::default()),
-                        span.get("column_start").unwrap_or(&Value::default()),
-                        span.get("line_end").unwrap_or(&Value::default()),
-                        span.get("column_end").unwrap_or(&Value::default()),
-                    ))
-                }
-            }
-            _ => None,
+        .span()
+        .map(|span| {
+            format!(
+                "{}:{}:{} - {}:{}",
+                file_name, span.line_start, span.column_start, span.line_end, span.column_end,
+            )
         })
-        .unwrap_or_else(|| None)
         .unwrap_or_else(|| "Span information not available".to_string())
 }
 
-fn extract_code_snippet(file_path: &Path, finding: &JsonFinding) -> Result<String> {
-    let no_spans = || "Span information not found in finding structure";
-
-    let spans = finding.spans().with_context(no_spans)?;
-    let first_span = spans.get(0).with_context(no_spans)?;
-
-    let byte_start = first_span
-        .get("byte_start")
-        .and_then(Value::as_u64)
-        .context("Byte start is missing in spans")?;
-    let byte_end = first_span
-        .get("byte_end")
-        .and_then(Value::as_u64)
-        .context("Byte end is missing in spans")?;
+pub(crate) fn extract_code_snippet(file_path: &Path, finding: &JsonFinding) -> Result<String> {
+    let span = finding
+        .span()
+        .context("Span information not found in finding structure")?;
+    let byte_start = u64::from(span.byte_start);
+    let byte_end = u64::from(span.byte_end);
 
     let file = std::fs::File::open(file_path)
         .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
@@ -267,5 +243,6 @@ fn create_summary(
         total_vulnerabilities,
         by_severity,
         table,
+        excluded_by_path: HashMap::new(),
     }
 }
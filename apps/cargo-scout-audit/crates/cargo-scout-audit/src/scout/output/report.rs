@@ -1,19 +1,28 @@
-use super::{html, markdown, pdf, utils};
+use super::{gitlab, html, junit, markdown, pdf, sarif, sonar, utils};
 use crate::{
     cli_args::OutputFormat,
     scout::{
-        core::project_info::Project,
+        core::{
+            baseline::BaselineFinding,
+            diff_filter::PreExistingFinding,
+            project_info::Project,
+            suppression::SuppressedFinding,
+            triage::{TriageEntry, TriageStore},
+        },
         finding::Finding as JsonFinding,
-        output::{raw_report::RawReport, table::Table},
+        output::{index::OutputIndex, raw_report::RawReport, table::Table},
+    },
+    util::{
+        detectors_info::{LintInfo, LintStore},
+        print::print_line,
     },
-    util::detectors_info::{LintInfo, LintStore},
 };
 use anyhow::{Context, Result};
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     fs::File,
-    io::Write,
     path::{Path, PathBuf},
 };
 use terminal_color_builder::OutputFormatter;
@@ -25,9 +34,23 @@ pub struct Report {
     pub summary: Summary,
     pub categories: Vec<Category>,
     pub findings: Vec<Finding>,
+    // Whether the run was compared to a `--baseline`, in which case the
+    // findings are the new ones.
+    #[serde(default)]
+    pub compared_to_baseline: bool,
+    #[serde(default)]
+    pub fixed_since_baseline: Vec<BaselineFinding>,
+    #[serde(default)]
+    pub suppressed_findings: Vec<SuppressedFinding>,
+    // Only filled when requested with `--show-pre-existing`.
+    #[serde(default)]
+    pub pre_existing_findings: Vec<PreExistingFinding>,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone)]
+// Variants are ordered from most to least severe.
+#[derive(
+    Serialize, Deserialize, Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, ValueEnum,
+)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Critical,
@@ -36,12 +59,42 @@ pub enum Severity {
     Enhancement,
 }
 
+impl Severity {
+    // Parses the severity names used in the detectors metadata (`LintInfo::severity`).
+    pub fn from_lint_severity(severity: &str) -> Option<Severity> {
+        match severity.to_lowercase().as_str() {
+            "critical" => Some(Severity::Critical),
+            "medium" => Some(Severity::Medium),
+            "minor" => Some(Severity::Minor),
+            "enhancement" => Some(Severity::Enhancement),
+            _ => None,
+        }
+    }
+
+    // Inverse of `from_lint_severity`.
+    pub fn as_lint_severity(&self) -> &'static str {
+        match self {
+            Severity::Critical => "Critical",
+            Severity::Medium => "Medium",
+            Severity::Minor => "Minor",
+            Severity::Enhancement => "Enhancement",
+        }
+    }
+
+    pub fn is_at_least(&self, threshold: Severity) -> bool {
+        *self <= threshold
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Summary {
     pub executed_on: Vec<Package>,
     pub total_vulnerabilities: u32,
     pub by_severity: HashMap<Severity, u32>,
     pub table: Table,
+    // Findings left out by the path filters, per detector.
+    #[serde(default)]
+    pub excluded_by_path: HashMap<String, u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -71,6 +124,8 @@ pub struct Vulnerability {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Finding {
+    // Position of the finding in the run, which the reports link on. It
+    // changes as other findings come and go, see `fingerprint`.
     pub id: u32,
     pub occurrence_index: u32,
     pub category_id: String,
@@ -80,6 +135,26 @@ pub struct Finding {
     pub code_snippet: String,
     pub package: String,
     pub file_path: String,
+    // Stays the same across runs while the flagged code and its enclosing
+    // item are unchanged, unlike `id`.
+    #[serde(default)]
+    pub fingerprint: String,
+    // Set when the finding was reused from a previous run of an unchanged package.
+    #[serde(default)]
+    pub from_cache: bool,
+    // The status recorded with `cargo scout-audit triage mark`, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub triage: Option<TriageEntry>,
+}
+
+// Findings that were analyzed but are not part of the report's findings.
+#[derive(Debug, Default)]
+pub struct OmittedFindings {
+    pub compared_to_baseline: bool,
+    pub fixed_since_baseline: Vec<BaselineFinding>,
+    pub excluded_by_path: HashMap<String, u32>,
+    pub suppressed: Vec<SuppressedFinding>,
+    pub pre_existing: Vec<PreExistingFinding>,
 }
 
 impl From<&LintInfo> for Vulnerability {
@@ -109,40 +184,117 @@ impl Report {
             summary,
             categories,
             findings,
+            compared_to_baseline: false,
+            fixed_since_baseline: Vec::new(),
+            suppressed_findings: Vec::new(),
+            pre_existing_findings: Vec::new(),
         }
     }
 
+    // The findings without a triage status, i.e. those still to act on. The
+    // CI formats that can't record a status leave the triaged ones out.
+    pub fn untriaged_findings(&self) -> impl Iterator<Item = &Finding> {
+        self.findings
+            .iter()
+            .filter(|finding| finding.triage.is_none())
+    }
+
+    // The report of a run, without writing it anywhere.
+    pub fn build(
+        findings: &[JsonFinding],
+        crates: &HashMap<String, bool>,
+        project_info: &Project,
+        detectors_info: &LintStore,
+        triage: &TriageStore,
+        omitted: OmittedFindings,
+    ) -> Result<Report> {
+        let mut report =
+            RawReport::generate_report(findings, crates, project_info, detectors_info)?;
+        for finding in report.findings.iter_mut() {
+            finding.triage = triage.find(&finding.fingerprint).cloned();
+        }
+        report.compared_to_baseline = omitted.compared_to_baseline;
+        report.fixed_since_baseline = omitted.fixed_since_baseline;
+        report.summary.excluded_by_path = omitted.excluded_by_path;
+        report.suppressed_findings = omitted.suppressed;
+        report.pre_existing_findings = omitted.pre_existing;
+        Ok(report)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     #[tracing::instrument(name = "GENERATE REPORT", skip_all)]
     pub fn generate(
-        findings: &Vec<JsonFinding>,
+        findings: &[JsonFinding],
         raw_findings: Vec<JsonFinding>,
         crates: &HashMap<String, bool>,
         project_info: Project,
         detectors_info: &LintStore,
         output_path: Option<PathBuf>,
+        output_dir: Option<PathBuf>,
+        template_dir: Option<PathBuf>,
         output_format: &[OutputFormat],
+        triage: &TriageStore,
+        omitted: OmittedFindings,
+        offline: bool,
     ) -> Result<()> {
-        let report = RawReport::generate_report(findings, crates, &project_info, detectors_info)?;
+        let report = Self::build(
+            findings,
+            crates,
+            &project_info,
+            detectors_info,
+            triage,
+            omitted,
+        )?;
 
         tracing::trace!(?output_format, "Output format");
         tracing::trace!(?report, "Report");
 
+        let mut index = OutputIndex::new(&report.name, &report.date);
+        if let Some(directory) = &output_dir {
+            std::fs::create_dir_all(directory)
+                .with_context(|| format!("Failed to create {}", directory.display()))?;
+        }
+
         for format in output_format.iter() {
-            let path = report.write_out(findings, &raw_findings, output_path.clone(), format)?;
+            let path = match &output_dir {
+                Some(directory) => Some(directory.join(format.file_name())),
+                None => output_path.clone(),
+            };
+            let path = report.write_out(
+                findings,
+                &raw_findings,
+                detectors_info,
+                path,
+                template_dir.as_deref(),
+                format,
+                offline,
+            )?;
 
             if let Some(path) = path {
-                let path = path
-                    .to_str()
-                    .with_context(|| "Path conversion to string failed")?;
-                let string = OutputFormatter::new()
-                    .fg()
-                    .green()
-                    .text_str(format!("{path} successfully generated.").as_str())
-                    .print();
-                println!("{string}");
+                if let Some(directory) = &output_dir {
+                    index.add(format, &path, directory);
+                }
+                Self::print_generated(&path)?;
             }
         }
 
+        if let Some(directory) = &output_dir {
+            Self::print_generated(&index.save(directory)?)?;
+        }
+
+        Ok(())
+    }
+
+    fn print_generated(path: &Path) -> Result<()> {
+        let path = path
+            .to_str()
+            .with_context(|| "Path conversion to string failed")?;
+        let string = OutputFormatter::new()
+            .fg()
+            .green()
+            .text_str(format!("{path} successfully generated.").as_str())
+            .print();
+        print_line(&string);
         Ok(())
     }
 
@@ -153,13 +305,17 @@ impl Report {
     }
 
     #[tracing::instrument(name = "GENERATING HTML FROM REPORT", level = "debug", skip_all)]
-    pub fn generate_html(&self) -> Result<String> {
-        html::generate_html(self)
+    pub fn generate_html(&self, template_dir: Option<&Path>) -> Result<String> {
+        html::generate_html(self, template_dir)
     }
 
     #[tracing::instrument(name = "GENERATING MARKDOWN FROM REPORT", level = "debug", skip_all)]
-    pub fn generate_markdown(&self, render_styles: bool) -> Result<String> {
-        markdown::generate_markdown(self, render_styles)
+    pub fn generate_markdown(
+        &self,
+        render_styles: bool,
+        template_dir: Option<&Path>,
+    ) -> Result<String> {
+        markdown::generate_markdown(self, render_styles, template_dir)
     }
 
     #[tracing::instrument(name = "GENERATING JSON FROM REPORT", level = "debug", skip_all)]
@@ -168,9 +324,53 @@ impl Report {
         Ok(json)
     }
 
+    #[tracing::instrument(name = "GENERATING SARIF FROM REPORT", level = "debug", skip_all)]
+    pub fn generate_sarif(
+        &self,
+        findings: &[JsonFinding],
+        detectors_info: &LintStore,
+    ) -> Result<String> {
+        sarif::generate_sarif(self, findings, detectors_info)
+    }
+
+    #[tracing::instrument(name = "GENERATING JUNIT FROM REPORT", level = "debug", skip_all)]
+    pub fn generate_junit(
+        &self,
+        findings: &[JsonFinding],
+        detectors_info: &LintStore,
+    ) -> Result<String> {
+        junit::generate_junit(self, findings, detectors_info)
+    }
+
+    #[tracing::instrument(
+        name = "GENERATING GITLAB CODE QUALITY FROM REPORT",
+        level = "debug",
+        skip_all
+    )]
+    pub fn generate_gitlab_code_quality(
+        &self,
+        findings: &[JsonFinding],
+        detectors_info: &LintStore,
+    ) -> Result<String> {
+        gitlab::generate_gitlab_code_quality(self, findings, detectors_info)
+    }
+
+    #[tracing::instrument(
+        name = "GENERATING SONAR ISSUES FROM REPORT",
+        level = "debug",
+        skip_all
+    )]
+    pub fn generate_sonar_generic(
+        &self,
+        findings: &[JsonFinding],
+        detectors_info: &LintStore,
+    ) -> Result<String> {
+        sonar::generate_sonar_generic(self, findings, detectors_info)
+    }
+
     #[tracing::instrument(name = "GENERATING PDF FROM REPORT", level = "debug", skip_all)]
-    pub fn generate_pdf(&self, path: &Path) -> Result<()> {
-        pdf::generate_pdf(path, self)
+    pub fn generate_pdf(&self, path: &Path, offline: bool) -> Result<()> {
+        pdf::generate_pdf(path, self, offline)
     }
 
     fn write_single_json(file: &mut File, findings: &[JsonFinding]) -> Result<()> {
@@ -198,17 +398,21 @@ impl Report {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn write_out(
         &self,
-        findings: &Vec<JsonFinding>,
+        findings: &[JsonFinding],
         raw_findings: &[JsonFinding],
+        detectors_info: &LintStore,
         output_path: Option<PathBuf>,
+        template_dir: Option<&Path>,
         output_format: &OutputFormat,
+        offline: bool,
     ) -> Result<Option<PathBuf>> {
         match output_format {
             OutputFormat::Html => {
                 // Generate HTML report
-                let html = self.generate_html()?;
+                let html = self.generate_html(template_dir)?;
 
                 // Save to file
                 let html_path = output_path.unwrap_or_else(|| PathBuf::from("report.html"));
@@ -259,7 +463,7 @@ impl Report {
             }
             OutputFormat::Markdown => {
                 // Generate Markdown
-                let markdown = self.generate_markdown(true)?;
+                let markdown = self.generate_markdown(true, template_dir)?;
 
                 // Save to file
                 let md_path = output_path.unwrap_or_else(|| PathBuf::from("report.md"));
@@ -269,7 +473,7 @@ impl Report {
             }
             OutputFormat::MarkdownGithub => {
                 // Generate Markdown
-                let markdown = self.generate_markdown(false)?;
+                let markdown = self.generate_markdown(false, template_dir)?;
 
                 // Save to file
                 let md_path = output_path.unwrap_or_else(|| PathBuf::from("report.md"));
@@ -278,35 +482,68 @@ impl Report {
                 Ok(Some(md_path))
             }
             OutputFormat::Sarif => {
-                let sarif_path = output_path.unwrap_or_else(|| PathBuf::from("report.sarif"));
-                let mut sarif_file = File::create(&sarif_path)?;
-
-                let mut child = std::process::Command::new("clippy-sarif")
-                    .stdin(std::process::Stdio::piped())
-                    .stdout(std::process::Stdio::piped())
-                    .spawn()?;
-
-                {
-                    let stdin = child.stdin.as_mut().unwrap();
-                    for finding in findings {
-                        writeln!(stdin, "{}", finding.json())?;
-                    }
-                }
-
-                let output = child.wait_with_output()?;
-                if !output.status.success() {
-                    return Err(anyhow::anyhow!("clippy-sarif failed to generate report"));
-                }
+                // Generate SARIF
+                let sarif = self.generate_sarif(findings, detectors_info)?;
 
-                std::io::Write::write_all(&mut sarif_file, &output.stdout)?;
+                // Save to file
+                let sarif_path = output_path.unwrap_or_else(|| PathBuf::from("report.sarif"));
+                self.save_to_file(&sarif_path, sarif)?;
 
                 Ok(Some(sarif_path))
             }
             OutputFormat::Pdf => {
                 let pdf_path = output_path.unwrap_or_else(|| PathBuf::from("report.pdf"));
-                self.generate_pdf(&pdf_path)?;
+                self.generate_pdf(&pdf_path, offline)?;
                 Ok(Some(pdf_path))
             }
+            OutputFormat::Junit => {
+                // Generate JUnit XML
+                let junit = self.generate_junit(findings, detectors_info)?;
+
+                // Save to file
+                let junit_path = output_path.unwrap_or_else(|| PathBuf::from("report.xml"));
+                self.save_to_file(&junit_path, junit)?;
+
+                Ok(Some(junit_path))
+            }
+            OutputFormat::GitlabCodeQuality => {
+                // Generate Code Quality report
+                let code_quality = self.generate_gitlab_code_quality(findings, detectors_info)?;
+
+                // Save to file
+                let code_quality_path =
+                    output_path.unwrap_or_else(|| PathBuf::from("gl-code-quality-report.json"));
+                self.save_to_file(&code_quality_path, code_quality)?;
+
+                Ok(Some(code_quality_path))
+            }
+            OutputFormat::SonarGeneric => {
+                // Generate Sonar generic issues
+                let sonar = self.generate_sonar_generic(findings, detectors_info)?;
+
+                // Save to file
+                let sonar_path = output_path.unwrap_or_else(|| PathBuf::from("sonar-issues.json"));
+                self.save_to_file(&sonar_path, sonar)?;
+
+                Ok(Some(sonar_path))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Severity;
+
+    #[test]
+    fn severity_threshold() {
+        assert!(Severity::Critical.is_at_least(Severity::Medium));
+        assert!(Severity::Medium.is_at_least(Severity::Medium));
+        assert!(!Severity::Enhancement.is_at_least(Severity::Minor));
+        assert_eq!(
+            Severity::from_lint_severity("Enhancement"),
+            Some(Severity::Enhancement)
+        );
+        assert_eq!(Severity::from_lint_severity("unknown"), None);
+    }
+}
@@ -0,0 +1,305 @@
+use super::{
+    report::{Finding, Report},
+    table::{Importance, Row, SemanticColor, Table},
+    utils,
+};
+use crate::{scout::core::fingerprint::fingerprint, util::print::print_info};
+use anyhow::{Context, Result};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::Write,
+    fs,
+    path::Path,
+};
+use terminal_color_builder::OutputFormatter;
+
+// Findings of two reports, matched by fingerprint. Line numbers play no part,
+// so a finding that only moved is unchanged.
+pub struct ReportDiff<'a> {
+    pub new: Vec<&'a Finding>,
+    pub fixed: Vec<&'a Finding>,
+    // As they appear in the newer report
+    pub unchanged: Vec<&'a Finding>,
+}
+
+impl<'a> ReportDiff<'a> {
+    pub fn compare(old: &'a Report, new: &'a Report) -> Self {
+        // Reports written before fingerprints were added are matched on the
+        // detector, file and code instead.
+        let fallback = old
+            .findings
+            .iter()
+            .chain(new.findings.iter())
+            .any(|finding| finding.fingerprint.is_empty());
+
+        let mut remaining = HashMap::<String, VecDeque<usize>>::new();
+        for (i, finding) in old.findings.iter().enumerate() {
+            remaining
+                .entry(match_key(finding, fallback))
+                .or_default()
+                .push_back(i);
+        }
+
+        let mut matched = vec![false; old.findings.len()];
+        let mut diff = ReportDiff {
+            new: Vec::new(),
+            fixed: Vec::new(),
+            unchanged: Vec::new(),
+        };
+        for finding in new.findings.iter() {
+            match remaining
+                .get_mut(&match_key(finding, fallback))
+                .and_then(VecDeque::pop_front)
+            {
+                Some(i) => {
+                    matched[i] = true;
+                    diff.unchanged.push(finding);
+                }
+                None => diff.new.push(finding),
+            }
+        }
+        diff.fixed = old
+            .findings
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(finding, _)| finding)
+            .collect();
+        diff
+    }
+
+    pub fn summary_table(&self) -> Table {
+        let mut header = Row::from_strs(&["New", "Fixed", "Unchanged"]);
+        header.set_color(SemanticColor::Importance(Importance::Header));
+        let mut table = Table::new(header);
+        let mut row = Row::from_strings(&[
+            self.new.len().to_string(),
+            self.fixed.len().to_string(),
+            self.unchanged.len().to_string(),
+        ]);
+        if !self.new.is_empty() {
+            row.get_mut(0).color = SemanticColor::Importance(Importance::Error);
+        }
+        table.add_row(row);
+        table
+    }
+
+    // `report` is the one the findings come from, for their severities.
+    pub fn findings_table(findings: &[&Finding], report: &Report) -> Table {
+        let mut header = Row::from_strs(&["Detector", "Severity", "Location", "Fingerprint"]);
+        header.set_color(SemanticColor::Importance(Importance::Header));
+        let mut table = Table::new(header);
+        for finding in findings {
+            table.add_row(Row::from_strings(&finding_cells(finding, report)));
+        }
+        table
+    }
+
+    pub fn to_markdown(&self, old: &Report, new: &Report) -> String {
+        let mut md = String::new();
+        // Writing to a String cannot fail
+        let _ = writeln!(md, "# Scout report comparison\n");
+        let _ = writeln!(
+            md,
+            "Comparing {} ({}) with {} ({}).\n",
+            old.name, old.date, new.name, new.date
+        );
+        let _ = writeln!(md, "| New | Fixed | Unchanged |");
+        let _ = writeln!(md, "| --- | ----- | --------- |");
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} |\n",
+            self.new.len(),
+            self.fixed.len(),
+            self.unchanged.len()
+        );
+
+        let _ = writeln!(md, "## New findings\n");
+        md.push_str(&markdown_table(&self.new, new, "No new findings."));
+        let _ = writeln!(md, "## Fixed findings\n");
+        md.push_str(&markdown_table(&self.fixed, old, "No findings were fixed."));
+        if !self.unchanged.is_empty() {
+            let _ = writeln!(
+                md,
+                "<details>\n<summary>Unchanged findings ({})</summary>\n",
+                self.unchanged.len()
+            );
+            md.push_str(&markdown_table(&self.unchanged, new, ""));
+            let _ = writeln!(md, "</details>");
+        }
+        md
+    }
+}
+
+fn match_key(finding: &Finding, fallback: bool) -> String {
+    if fallback {
+        fingerprint(
+            &finding.vulnerability_id,
+            "",
+            &finding.file_path,
+            "",
+            &finding.code_snippet,
+        )
+    } else {
+        finding.fingerprint.clone()
+    }
+}
+
+fn finding_cells(finding: &Finding, report: &Report) -> [String; 4] {
+    let severity = report
+        .categories
+        .iter()
+        .flat_map(|category| category.vulnerabilities.iter())
+        .find(|vulnerability| vulnerability.id == finding.vulnerability_id)
+        .map(|vulnerability| utils::capitalize(&vulnerability.severity))
+        .unwrap_or_default();
+    // The span starts with the bare file name, followed by the line
+    let location = match finding.span.split(':').nth(1) {
+        Some(line) => format!("{}:{}", finding.file_path, line),
+        None => finding.file_path.clone(),
+    };
+    let short_fingerprint = finding.fingerprint.chars().take(12).collect();
+    [
+        finding.vulnerability_id.clone(),
+        severity,
+        location,
+        short_fingerprint,
+    ]
+}
+
+fn markdown_table(findings: &[&Finding], report: &Report, empty: &str) -> String {
+    if findings.is_empty() {
+        return format!("{}\n\n", empty);
+    }
+    let mut md = String::from(
+        "| Detector | Severity | Location | Fingerprint |\n\
+         | -------- | -------- | -------- | ----------- |\n",
+    );
+    for finding in findings {
+        let [detector, severity, location, fingerprint] = finding_cells(finding, report);
+        let _ = writeln!(
+            md,
+            "| {} | {} | {} | `{}` |",
+            detector,
+            severity,
+            location.replace('|', "\\|"),
+            fingerprint
+        );
+    }
+    md.push('\n');
+    md
+}
+
+fn load_report(path: &Path) -> Result<Report> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report: {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| {
+        format!(
+            "Failed to parse report: {}. Use a report written with `--output-format json`",
+            path.display()
+        )
+    })
+}
+
+// Runs `cargo scout-audit diff`.
+pub fn diff_reports(old_path: &Path, new_path: &Path, markdown: Option<&Path>) -> Result<()> {
+    let old = load_report(old_path)?;
+    let new = load_report(new_path)?;
+    let diff = ReportDiff::compare(&old, &new);
+
+    diff.summary_table().print();
+    for (title, findings, report) in [
+        ("New findings", &diff.new, &new),
+        ("Fixed findings", &diff.fixed, &old),
+    ] {
+        if findings.is_empty() {
+            continue;
+        }
+        let title = OutputFormatter::new()
+            .fg()
+            .green()
+            .text_str(format!("\n{} ({})", title, findings.len()).as_str())
+            .print();
+        println!("{}", title);
+        ReportDiff::findings_table(findings, report).print();
+    }
+
+    if let Some(path) = markdown {
+        utils::write_to_file(&path.to_path_buf(), diff.to_markdown(&old, &new).as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        print_info(&format!("Comparison written to {}", path.display()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReportDiff;
+    use crate::scout::output::report::Report;
+    use serde_json::json;
+
+    fn report(findings: &[(&str, &str, &str)]) -> Report {
+        let findings = findings
+            .iter()
+            .enumerate()
+            .map(|(id, (detector, span, fingerprint))| {
+                json!({
+                    "id": id,
+                    "occurrence_index": 1,
+                    "category_id": "error-handling",
+                    "vulnerability_id": detector,
+                    "error_message": "message",
+                    "span": span,
+                    "code_snippet": "x.unwrap()",
+                    "package": "token",
+                    "file_path": "token/src/lib.rs",
+                    "fingerprint": fingerprint,
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::from_value(json!({
+            "name": "token",
+            "date": "2026-10-18",
+            "summary": {
+                "executed_on": [],
+                "total_vulnerabilities": findings.len(),
+                "by_severity": {},
+                "table": { "header": { "cells": [] }, "rows": [] },
+            },
+            "categories": [],
+            "findings": findings,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn matches_findings_across_line_shifts() {
+        let old = report(&[
+            ("unsafe_unwrap", "lib.rs:10:5 - 10:15", "a"),
+            ("unsafe_expect", "lib.rs:20:5 - 20:15", "b"),
+        ]);
+        let new = report(&[
+            ("unsafe_unwrap", "lib.rs:14:5 - 14:15", "a"),
+            ("divide_before_multiply", "lib.rs:30:5 - 30:15", "c"),
+        ]);
+
+        let diff = ReportDiff::compare(&old, &new);
+        assert_eq!(diff.unchanged[0].span, "lib.rs:14:5 - 14:15");
+        assert_eq!(diff.new[0].vulnerability_id, "divide_before_multiply");
+        assert_eq!(diff.fixed[0].vulnerability_id, "unsafe_expect");
+
+        let md = diff.to_markdown(&old, &new);
+        assert!(md.contains("| 1 | 1 | 1 |"));
+        assert!(md.contains("| divide_before_multiply |  | token/src/lib.rs:30 | `c` |"));
+    }
+
+    #[test]
+    fn falls_back_without_fingerprints() {
+        let old = report(&[("unsafe_unwrap", "lib.rs:10:5 - 10:15", "")]);
+        let new = report(&[("unsafe_unwrap", "lib.rs:12:5 - 12:15", "a")]);
+
+        let diff = ReportDiff::compare(&old, &new);
+        assert_eq!(diff.unchanged.len(), 1);
+        assert!(diff.new.is_empty() && diff.fixed.is_empty());
+    }
+}
@@ -0,0 +1,484 @@
+use super::model::{
+    ArtifactChange, ArtifactContent, ArtifactLocation, BaselineState, CodeFlow, Fix, Level,
+    Location, Message, PhysicalLocation, Region, Replacement, ReportingConfiguration,
+    ReportingDescriptor, RuleProperties, Run, SARIF_SCHEMA, SARIF_VERSION, Sarif, SarifResult,
+    Suppression, SuppressionKind, SuppressionStatus, ThreadFlow, ThreadFlowLocation, Tool,
+    ToolComponent,
+};
+use crate::{
+    scout::{
+        core::{baseline::BaselineFinding, triage::TriageEntry},
+        diagnostic::DiagnosticSpan,
+        finding::Finding as JsonFinding,
+        output::report::{Finding, Report, Severity},
+    },
+    util::detectors_info::{LintInfo, LintStore},
+};
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+
+const SCOUT_URI: &str = "https://github.com/CoinFabrik/scout-audit";
+const FINGERPRINT_KEY: &str = "scoutFingerprint/v1";
+
+// Generates a SARIF 2.1.0 log from a given `Report` object.
+pub fn generate_sarif(
+    report: &Report,
+    findings: &[JsonFinding],
+    detectors_info: &LintStore,
+) -> Result<String> {
+    let mut lints = detectors_info.iter().collect::<Vec<_>>();
+    lints.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let rule_indices = lints
+        .iter()
+        .enumerate()
+        .map(|(index, lint)| (lint.id.as_str(), index))
+        .collect::<HashMap<_, _>>();
+
+    // Compared to a baseline, the findings are the new ones, and the fixed
+    // ones are reported as absent.
+    let baseline_state = report.compared_to_baseline.then_some(BaselineState::New);
+    let results = report
+        .findings
+        .iter()
+        .filter_map(|finding| {
+            let json_finding = findings.get(finding.id as usize)?;
+            let rule_index = *rule_indices.get(finding.vulnerability_id.as_str())?;
+            Some(SarifResult {
+                baseline_state,
+                ..generate_result(finding, json_finding, lints[rule_index], rule_index)
+            })
+        })
+        .chain(report.fixed_since_baseline.iter().filter_map(|finding| {
+            let rule_index = *rule_indices.get(finding.vulnerability_id.as_str())?;
+            Some(generate_absent_result(
+                finding,
+                lints[rule_index],
+                rule_index,
+            ))
+        }))
+        .collect();
+
+    let sarif = Sarif {
+        schema: SARIF_SCHEMA,
+        version: SARIF_VERSION,
+        runs: vec![Run {
+            tool: Tool {
+                driver: ToolComponent {
+                    name: "scout".to_string(),
+                    information_uri: SCOUT_URI.to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    rules: lints.into_iter().map(generate_rule).collect(),
+                },
+            },
+            results,
+        }],
+    };
+
+    Ok(serde_json::to_string_pretty(&sarif)?)
+}
+
+fn level(severity: Option<Severity>) -> Level {
+    match severity {
+        Some(Severity::Critical) => Level::Error,
+        Some(Severity::Medium) | None => Level::Warning,
+        Some(Severity::Minor) | Some(Severity::Enhancement) => Level::Note,
+    }
+}
+
+// Scores follow the ranges GitHub code scanning uses to bucket alerts.
+fn security_severity(severity: Option<Severity>) -> &'static str {
+    match severity {
+        Some(Severity::Critical) => "9.0",
+        Some(Severity::Medium) | None => "5.5",
+        Some(Severity::Minor) => "3.0",
+        Some(Severity::Enhancement) => "1.0",
+    }
+}
+
+fn generate_rule(lint: &LintInfo) -> ReportingDescriptor {
+    let severity = Severity::from_lint_severity(&lint.severity);
+    let mut tags = vec!["security".to_string()];
+    if !lint.vulnerability_class.is_empty() {
+        tags.push(lint.vulnerability_class.clone());
+    }
+
+    ReportingDescriptor {
+        id: lint.id.clone(),
+        name: lint.name.clone(),
+        short_description: Message {
+            text: lint.short_message.clone(),
+        },
+        full_description: Message {
+            text: lint.long_message.clone(),
+        },
+        help_uri: lint.help.clone(),
+        help: Message {
+            text: format!("{}\n\n{}", lint.long_message, lint.help)
+                .trim()
+                .to_string(),
+        },
+        default_configuration: ReportingConfiguration {
+            level: level(severity),
+        },
+        properties: RuleProperties {
+            tags,
+            security_severity: security_severity(severity).to_string(),
+            problem_severity: lint.severity.to_lowercase(),
+        },
+    }
+}
+
+fn generate_result(
+    finding: &Finding,
+    json_finding: &JsonFinding,
+    lint: &LintInfo,
+    rule_index: usize,
+) -> SarifResult {
+    let (primary, secondary) = parse_locations(json_finding);
+    let file_path = json_finding
+        .file_name()
+        .unwrap_or_default()
+        .replace('\\', "/");
+
+    let primary = primary.unwrap_or_else(|| Location {
+        id: None,
+        physical_location: PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: file_path.clone(),
+            },
+            region: None,
+        },
+        message: None,
+    });
+    let mut location = primary.clone();
+    if let Some(region) = location.physical_location.region.as_mut() {
+        region.snippet = Some(Message {
+            text: finding.code_snippet.clone(),
+        });
+    }
+
+    // Detectors that point at more than one span get the whole path as a code flow.
+    let code_flows = if secondary.is_empty() {
+        Vec::new()
+    } else {
+        vec![CodeFlow {
+            thread_flows: vec![ThreadFlow {
+                locations: std::iter::once(&primary)
+                    .chain(secondary.iter())
+                    .map(|location| ThreadFlowLocation {
+                        location: Location {
+                            id: None,
+                            ..location.clone()
+                        },
+                    })
+                    .collect(),
+            }],
+        }]
+    };
+
+    let partial_fingerprints =
+        BTreeMap::from([(FINGERPRINT_KEY.to_string(), finding.fingerprint.clone())]);
+
+    SarifResult {
+        rule_id: lint.id.clone(),
+        rule_index,
+        level: level(Severity::from_lint_severity(&lint.severity)),
+        message: Message {
+            text: finding.error_message.clone(),
+        },
+        locations: vec![location],
+        partial_fingerprints,
+        related_locations: secondary,
+        code_flows,
+        fixes: parse_fixes(json_finding),
+        baseline_state: None,
+        suppressions: finding.triage.iter().map(triage_suppression).collect(),
+    }
+}
+
+// The baseline only keeps the file of a finding, not its span.
+fn generate_absent_result(
+    finding: &BaselineFinding,
+    lint: &LintInfo,
+    rule_index: usize,
+) -> SarifResult {
+    SarifResult {
+        rule_id: lint.id.clone(),
+        rule_index,
+        level: level(Severity::from_lint_severity(&lint.severity)),
+        message: Message {
+            text: finding.error_message.clone(),
+        },
+        locations: vec![Location {
+            id: None,
+            physical_location: PhysicalLocation {
+                artifact_location: ArtifactLocation {
+                    uri: finding.file_path.clone(),
+                },
+                region: None,
+            },
+            message: None,
+        }],
+        partial_fingerprints: BTreeMap::from([(
+            FINGERPRINT_KEY.to_string(),
+            finding.fingerprint.clone(),
+        )]),
+        related_locations: Vec::new(),
+        code_flows: Vec::new(),
+        fixes: Vec::new(),
+        baseline_state: Some(BaselineState::Absent),
+        suppressions: Vec::new(),
+    }
+}
+
+// Triaged findings are kept as suppressed results, so code scanning tools
+// dismiss their alerts rather than closing them as fixed.
+fn triage_suppression(entry: &TriageEntry) -> Suppression {
+    Suppression {
+        kind: SuppressionKind::External,
+        status: SuppressionStatus::Accepted,
+        justification: if entry.comment.is_empty() {
+            entry.status.to_string()
+        } else {
+            format!("{}: {}", entry.status, entry.comment)
+        },
+    }
+}
+
+// Splits the spans of a finding into its main location and the related ones.
+// As in the other reports, the main one is the primary span, which is also
+// where the code snippet comes from.
+fn parse_locations(finding: &JsonFinding) -> (Option<Location>, Vec<Location>) {
+    let primary = finding.span().map(parse_location);
+    let secondary = finding
+        .secondary_spans()
+        .into_iter()
+        .map(parse_location)
+        .enumerate()
+        .map(|(index, location)| Location {
+            id: Some(index + 1),
+            ..location
+        })
+        .collect();
+
+    (primary, secondary)
+}
+
+fn parse_location(span: &DiagnosticSpan) -> Location {
+    Location {
+        id: None,
+        physical_location: PhysicalLocation {
+            artifact_location: artifact_location(span),
+            region: Some(region(span)),
+        },
+        message: span.label.clone().map(|text| Message { text }),
+    }
+}
+
+// One fix per suggestion of the detector, each replacing the code of its span.
+fn parse_fixes(finding: &JsonFinding) -> Vec<Fix> {
+    let Some(diagnostic) = finding.diagnostic() else {
+        return Vec::new();
+    };
+    diagnostic
+        .children
+        .iter()
+        .filter(|child| {
+            child
+                .spans
+                .iter()
+                .any(|span| span.suggested_replacement.is_some())
+        })
+        .map(|child| Fix {
+            description: Message {
+                text: child.message.clone(),
+            },
+            artifact_changes: child
+                .spans
+                .iter()
+                .filter_map(|span| {
+                    Some(ArtifactChange {
+                        artifact_location: artifact_location(span),
+                        replacements: vec![Replacement {
+                            deleted_region: region(span),
+                            inserted_content: ArtifactContent {
+                                text: span.suggested_replacement.clone()?,
+                            },
+                        }],
+                    })
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+fn artifact_location(span: &DiagnosticSpan) -> ArtifactLocation {
+    ArtifactLocation {
+        uri: span.file_name.replace('\\', "/"),
+    }
+}
+
+fn region(span: &DiagnosticSpan) -> Region {
+    Region {
+        start_line: span.line_start as u64,
+        start_column: span.column_start as u64,
+        end_line: span.line_end as u64,
+        end_column: span.column_end as u64,
+        snippet: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate_sarif, parse_fixes, parse_locations, triage_suppression};
+    use crate::{
+        scout::{
+            core::triage::{TriageEntry, TriageStatus},
+            finding::Finding,
+            output::report::Report,
+        },
+        util::detectors_info::{LintInfo, LintStore},
+    };
+    use serde_json::{Value, json};
+
+    fn span(line: u64, is_primary: bool) -> serde_json::Value {
+        json!({
+            "file_name": "src/lib.rs",
+            "line_start": line,
+            "line_end": line,
+            "column_start": 5,
+            "column_end": 10,
+            "is_primary": is_primary,
+            "label": null,
+        })
+    }
+
+    #[test]
+    fn primary_span_is_the_main_location() {
+        let finding = Finding::new(json!({
+            "message": { "spans": [span(3, false), span(7, true), span(9, false)] },
+        }));
+
+        let (primary, secondary) = parse_locations(&finding);
+
+        let primary = primary.unwrap().physical_location.region.unwrap();
+        assert_eq!(primary.start_line, 7);
+        assert_eq!(
+            secondary
+                .iter()
+                .map(|location| location.id)
+                .collect::<Vec<_>>(),
+            [Some(1), Some(2)]
+        );
+    }
+
+    #[test]
+    fn suggestions_become_fixes() {
+        let mut suggestion = span(7, true);
+        suggestion["suggested_replacement"] = json!("x.unwrap_or_default()");
+        let finding = Finding::new(json!({
+            "message": {
+                "spans": [span(7, true)],
+                "children": [
+                    { "message": "consider checking the value", "spans": [] },
+                    { "message": "use `unwrap_or_default`", "spans": [suggestion] },
+                ],
+            },
+        }));
+
+        let fixes = parse_fixes(&finding);
+
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].description.text, "use `unwrap_or_default`");
+        let replacement = &fixes[0].artifact_changes[0].replacements[0];
+        assert_eq!(replacement.deleted_region.start_line, 7);
+        assert_eq!(replacement.inserted_content.text, "x.unwrap_or_default()");
+    }
+
+    #[test]
+    fn baseline_runs_mark_new_and_absent_results() {
+        let report: Report = serde_json::from_value(json!({
+            "name": "token",
+            "date": "2026-10-18",
+            "summary": {
+                "executed_on": [],
+                "total_vulnerabilities": 1,
+                "by_severity": {},
+                "table": { "header": { "cells": [] }, "rows": [] },
+            },
+            "categories": [],
+            "findings": [{
+                "id": 0,
+                "occurrence_index": 1,
+                "category_id": "error-handling",
+                "vulnerability_id": "unsafe_unwrap",
+                "error_message": "Unsafe usage of `unwrap`",
+                "span": "lib.rs:7:5 - 7:10",
+                "code_snippet": "x.unwrap()",
+                "package": "token",
+                "file_path": "src/lib.rs",
+                "fingerprint": "a",
+            }],
+            "compared_to_baseline": true,
+            "fixed_since_baseline": [{
+                "fingerprint": "b",
+                "vulnerability_id": "unsafe_unwrap",
+                "package": "token",
+                "file_path": "src/vault.rs",
+                "code_snippet": "y.unwrap()",
+                "error_message": "Unsafe usage of `unwrap`",
+            }],
+        }))
+        .unwrap();
+        let findings = [Finding::new(json!({
+            "message": { "spans": [span(7, true)] },
+        }))];
+        let mut lints = LintStore::new();
+        lints.insert(LintInfo {
+            id: "unsafe_unwrap".to_string(),
+            severity: "Minor".to_string(),
+            ..Default::default()
+        });
+
+        let sarif = generate_sarif(&report, &findings, &lints).unwrap();
+        let sarif = serde_json::from_str::<Value>(&sarif).unwrap();
+
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["baselineState"], "new");
+        assert_eq!(results[1]["baselineState"], "absent");
+        assert_eq!(
+            results[1]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "src/vault.rs"
+        );
+        assert_eq!(
+            results[1]["partialFingerprints"]["scoutFingerprint/v1"],
+            "b"
+        );
+    }
+
+    #[test]
+    fn triaged_findings_are_accepted_suppressions() {
+        let mut entry = TriageEntry {
+            fingerprint: "a".to_string(),
+            status: TriageStatus::FalsePositive,
+            author: "auditor".to_string(),
+            comment: "checked above".to_string(),
+            date: "2026-10-18".to_string(),
+        };
+
+        let suppression = serde_json::to_value(triage_suppression(&entry)).unwrap();
+        assert_eq!(
+            suppression,
+            json!({
+                "kind": "external",
+                "status": "accepted",
+                "justification": "false-positive: checked above",
+            })
+        );
+
+        entry.comment.clear();
+        assert_eq!(triage_suppression(&entry).justification, "false-positive");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod lib;
+pub use lib::generate_sarif;
+mod model;
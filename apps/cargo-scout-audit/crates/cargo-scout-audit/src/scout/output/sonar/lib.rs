@@ -0,0 +1,137 @@
+use super::model::{
+    Impact, ImpactSeverity, Issue, Location, Rule, SoftwareQuality, SonarReport, TextRange,
+};
+use crate::{
+    scout::{
+        diagnostic::DiagnosticSpan,
+        finding::Finding as JsonFinding,
+        output::report::{Report, Severity},
+    },
+    util::detectors_info::{LintInfo, LintStore},
+};
+use anyhow::Result;
+
+const ENGINE_ID: &str = "scout";
+
+// Generates a SonarQube generic issue report from a given `Report` object.
+pub fn generate_sonar_generic(
+    report: &Report,
+    findings: &[JsonFinding],
+    detectors_info: &LintStore,
+) -> Result<String> {
+    let mut lints = detectors_info.iter().collect::<Vec<_>>();
+    lints.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let issues = report
+        .untriaged_findings()
+        .filter_map(|finding| {
+            let json_finding = findings.get(finding.id as usize)?;
+            detectors_info.find_by_id(&finding.vulnerability_id)?;
+            let (primary, secondary) = parse_locations(json_finding, &finding.error_message);
+            Some(Issue {
+                rule_id: finding.vulnerability_id.clone(),
+                primary_location: primary.unwrap_or_else(|| Location {
+                    message: finding.error_message.clone(),
+                    file_path: finding.file_path.replace('\\', "/"),
+                    text_range: None,
+                }),
+                secondary_locations: secondary,
+            })
+        })
+        .collect();
+
+    let sonar = SonarReport {
+        rules: lints.into_iter().map(generate_rule).collect(),
+        issues,
+    };
+
+    Ok(serde_json::to_string_pretty(&sonar)?)
+}
+
+fn generate_rule(lint: &LintInfo) -> Rule {
+    let severity = Severity::from_lint_severity(&lint.severity);
+    let (software_quality, clean_code_attribute) = match severity {
+        Some(Severity::Enhancement) => (SoftwareQuality::Maintainability, "CONVENTIONAL"),
+        _ => (SoftwareQuality::Security, "TRUSTWORTHY"),
+    };
+    let description = if lint.help.is_empty() {
+        lint.long_message.clone()
+    } else {
+        format!("{}\n\nLearn more: {}", lint.long_message, lint.help)
+    };
+
+    Rule {
+        id: lint.id.clone(),
+        name: lint.name.clone(),
+        description: description.trim().to_string(),
+        engine_id: ENGINE_ID,
+        clean_code_attribute,
+        impacts: vec![Impact {
+            software_quality,
+            severity: impact_severity(severity),
+        }],
+    }
+}
+
+fn impact_severity(severity: Option<Severity>) -> ImpactSeverity {
+    match severity {
+        Some(Severity::Critical) => ImpactSeverity::High,
+        Some(Severity::Medium) | None => ImpactSeverity::Medium,
+        Some(Severity::Minor) | Some(Severity::Enhancement) => ImpactSeverity::Low,
+    }
+}
+
+// As in the SARIF report, the primary span is the main location and the rest
+// are related ones.
+fn parse_locations(finding: &JsonFinding, message: &str) -> (Option<Location>, Vec<Location>) {
+    let primary = finding.span().map(|span| parse_location(span, message));
+    let secondary = finding
+        .secondary_spans()
+        .into_iter()
+        .map(|span| parse_location(span, message))
+        .collect();
+    (primary, secondary)
+}
+
+// Sonar columns are 0-based, while rustc columns start at 1.
+fn parse_location(span: &DiagnosticSpan, message: &str) -> Location {
+    Location {
+        message: span.label.clone().unwrap_or_else(|| message.to_string()),
+        file_path: span.file_name.replace('\\', "/"),
+        text_range: Some(TextRange {
+            start_line: span.line_start as u64,
+            end_line: span.line_end as u64,
+            start_column: span.column_start.saturating_sub(1) as u64,
+            end_column: span.column_end.saturating_sub(1) as u64,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_locations;
+    use crate::scout::finding::Finding;
+    use serde_json::json;
+
+    #[test]
+    fn columns_are_zero_based() {
+        let finding = Finding::new(json!({
+            "message": { "spans": [{
+                "file_name": "src\\lib.rs",
+                "line_start": 3,
+                "line_end": 3,
+                "column_start": 5,
+                "column_end": 12,
+                "label": null,
+            }] },
+        }));
+
+        let (primary, secondary) = parse_locations(&finding, "unsafe unwrap");
+        let primary = primary.unwrap();
+        let range = primary.text_range.unwrap();
+        assert_eq!(primary.file_path, "src/lib.rs");
+        assert_eq!(primary.message, "unsafe unwrap");
+        assert_eq!((range.start_column, range.end_column), (4, 11));
+        assert!(secondary.is_empty());
+    }
+}
@@ -0,0 +1,3 @@
+pub mod lib;
+pub use lib::generate_sonar_generic;
+mod model;
@@ -0,0 +1,69 @@
+// Subset of the SonarQube generic issue import format (10.3 and later).
+// https://docs.sonarsource.com/sonarqube-server/latest/analyzing-source-code/importing-external-issues/generic-issue-import-format/
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+pub struct SonarReport {
+    pub rules: Vec<Rule>,
+    pub issues: Vec<Issue>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Rule {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub engine_id: &'static str,
+    pub clean_code_attribute: &'static str,
+    pub impacts: Vec<Impact>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Impact {
+    pub software_quality: SoftwareQuality,
+    pub severity: ImpactSeverity,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SoftwareQuality {
+    Security,
+    Maintainability,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ImpactSeverity {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Issue {
+    pub rule_id: String,
+    pub primary_location: Location,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub secondary_locations: Vec<Location>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    pub message: String,
+    pub file_path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text_range: Option<TextRange>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TextRange {
+    pub start_line: u64,
+    pub end_line: u64,
+    pub start_column: u64,
+    pub end_column: u64,
+}
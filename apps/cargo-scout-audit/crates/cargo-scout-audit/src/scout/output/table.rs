@@ -317,6 +317,12 @@ impl Table {
     pub fn to_json_table(&self) -> Value {
         Value::Object(self.to_json_map())
     }
+    pub fn header(&self) -> &'_ Row {
+        &self.header
+    }
+    pub fn rows(&self) -> Iter<'_, Row> {
+        self.rows.iter()
+    }
     pub fn get(&self, i: usize) -> &'_ Row {
         &self.rows[i]
     }
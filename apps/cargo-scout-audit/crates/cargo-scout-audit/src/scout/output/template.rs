@@ -0,0 +1,37 @@
+use super::{html, markdown};
+use crate::util::print::print_info;
+use anyhow::{Context, Result, bail};
+use std::{fs, path::Path};
+
+// Writes the built-in HTML and Markdown templates to `directory`, as a starting
+// point for `--template`. Existing files are never overwritten.
+pub fn export_templates(directory: &Path) -> Result<()> {
+    let templates = html::tera::TEMPLATES
+        .into_iter()
+        .chain([(markdown::tera::TEMPLATE_FILE, markdown::tera::TEMPLATE_STR)])
+        .collect::<Vec<_>>();
+
+    let existing = templates
+        .iter()
+        .filter(|(name, _)| directory.join(name).exists())
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>();
+    if !existing.is_empty() {
+        bail!(
+            "{} already contains {}",
+            directory.display(),
+            existing.join(", ")
+        );
+    }
+
+    fs::create_dir_all(directory)
+        .with_context(|| format!("Failed to create {}", directory.display()))?;
+    for (name, contents) in templates {
+        let path = directory.join(name);
+        fs::write(&path, contents)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+
+    print_info(&format!("Templates exported to {}", directory.display()));
+    Ok(())
+}
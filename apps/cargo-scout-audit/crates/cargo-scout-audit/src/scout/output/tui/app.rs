@@ -0,0 +1,451 @@
+use crate::{
+    scout::{
+        core::triage::{TriageEntry, TriageStatus, TriageStore, default_author},
+        finding::Finding,
+        output::report::Severity,
+    },
+    util::{detectors_info::LintStore, print::strip_ansi},
+};
+use anyhow::Result;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+
+const PAGE: isize = 10;
+
+// A finding as shown in the TUI, resolved once against the detectors metadata.
+pub struct Entry {
+    pub detector: String,
+    pub name: String,
+    pub category: String,
+    pub severity: Option<Severity>,
+    pub package: String,
+    pub file_path: String,
+    pub line: u64,
+    pub column: u64,
+    pub rendered: String,
+    pub snippet: String,
+    pub fingerprint: String,
+    pub triage: Option<TriageStatus>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    Category,
+    Severity,
+    Package,
+}
+
+impl GroupBy {
+    fn next(self) -> Self {
+        match self {
+            GroupBy::Category => GroupBy::Severity,
+            GroupBy::Severity => GroupBy::Package,
+            GroupBy::Package => GroupBy::Category,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            GroupBy::Category => "category",
+            GroupBy::Severity => "severity",
+            GroupBy::Package => "package",
+        }
+    }
+
+    // Groups are sorted by the number, then by the title.
+    fn key(self, entry: &Entry) -> (usize, String) {
+        match self {
+            GroupBy::Category => (0, entry.category.clone()),
+            GroupBy::Severity => match entry.severity {
+                Some(severity) => (severity as usize, severity.as_lint_severity().to_string()),
+                None => (usize::MAX, "Unknown".to_string()),
+            },
+            GroupBy::Package => (0, entry.package.clone()),
+        }
+    }
+}
+
+pub enum ListRow {
+    Group { title: String, count: usize },
+    // Index into `App::entries`
+    Finding(usize),
+}
+
+pub enum Action {
+    None,
+    Quit,
+    OpenEditor(PathBuf, u64),
+}
+
+pub struct App {
+    pub entries: Vec<Entry>,
+    pub rows: Vec<ListRow>,
+    // Index into `rows`, always of a finding
+    pub selected: Option<usize>,
+    pub group_by: GroupBy,
+    pub filter: String,
+    pub editing_filter: bool,
+    pub detail_scroll: u16,
+    pub status: String,
+    triage: TriageStore,
+    workspace_root: PathBuf,
+}
+
+impl App {
+    pub fn new(
+        findings: &[Finding],
+        detectors_info: &LintStore,
+        triage: TriageStore,
+        workspace_root: &Path,
+    ) -> Self {
+        let entries = findings
+            .iter()
+            .map(|finding| {
+                let detector = finding.code();
+                let lint = detectors_info.find_by_id(&detector);
+                let fingerprint = finding.fingerprint().to_string();
+                let span = finding.span().cloned().unwrap_or_default();
+                Entry {
+                    name: lint.map_or_else(|| detector.clone(), |lint| lint.name.clone()),
+                    category: lint
+                        .map(|lint| lint.vulnerability_class.clone())
+                        .unwrap_or_default(),
+                    severity: lint.and_then(|lint| Severity::from_lint_severity(&lint.severity)),
+                    package: finding.package(),
+                    file_path: finding.file_name().unwrap_or_default(),
+                    line: span.line_start as u64,
+                    column: span.column_start as u64,
+                    rendered: strip_ansi(&finding.rendered()),
+                    snippet: finding.snippet().to_string(),
+                    triage: triage.find(&fingerprint).map(|entry| entry.status),
+                    fingerprint,
+                    detector,
+                }
+            })
+            .collect();
+
+        let mut app = App {
+            entries,
+            rows: Vec::new(),
+            selected: None,
+            group_by: GroupBy::Category,
+            filter: String::new(),
+            editing_filter: false,
+            detail_scroll: 0,
+            status: String::new(),
+            triage,
+            workspace_root: workspace_root.to_path_buf(),
+        };
+        app.rebuild();
+        app
+    }
+
+    pub fn selected_entry(&self) -> Option<&Entry> {
+        self.selected_index().map(|index| &self.entries[index])
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|row| matches!(row, ListRow::Finding(_)))
+            .count()
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Action {
+        if self.editing_filter {
+            match key.code {
+                KeyCode::Enter => self.editing_filter = false,
+                KeyCode::Esc => {
+                    self.editing_filter = false;
+                    self.set_filter(String::new());
+                }
+                KeyCode::Backspace => {
+                    let mut filter = self.filter.clone();
+                    filter.pop();
+                    self.set_filter(filter);
+                }
+                KeyCode::Char(c) => self.set_filter(format!("{}{}", self.filter, c)),
+                _ => {}
+            }
+            return Action::None;
+        }
+
+        self.status.clear();
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Action::Quit;
+        }
+        match key.code {
+            KeyCode::Char('q') => return Action::Quit,
+            KeyCode::Esc if self.filter.is_empty() => return Action::Quit,
+            KeyCode::Esc => self.set_filter(String::new()),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(PAGE),
+            KeyCode::PageUp => self.move_selection(-PAGE),
+            KeyCode::End => self.move_selection(self.rows.len() as isize),
+            KeyCode::Home => self.move_selection(-(self.rows.len() as isize)),
+            KeyCode::Char('J') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+            KeyCode::Char('K') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+            KeyCode::Char('g') => {
+                self.group_by = self.group_by.next();
+                self.rebuild();
+            }
+            KeyCode::Char('/') => self.editing_filter = true,
+            KeyCode::Char('f') => self.set_triage(Some(TriageStatus::FalsePositive)),
+            KeyCode::Char('a') => self.set_triage(Some(TriageStatus::AcceptedRisk)),
+            KeyCode::Char('w') => self.set_triage(Some(TriageStatus::WontFix)),
+            KeyCode::Char('u') => self.set_triage(None),
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(entry) = self.selected_entry() {
+                    return Action::OpenEditor(
+                        self.workspace_root.join(&entry.file_path),
+                        entry.line.max(1),
+                    );
+                }
+            }
+            _ => {}
+        }
+        Action::None
+    }
+
+    fn selected_index(&self) -> Option<usize> {
+        match self.rows.get(self.selected?)? {
+            ListRow::Finding(index) => Some(*index),
+            ListRow::Group { .. } => None,
+        }
+    }
+
+    fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.rebuild();
+    }
+
+    // Lays out the rows for the current grouping and filter, keeping the
+    // selected finding if it is still listed.
+    fn rebuild(&mut self) {
+        let previous = self.selected_index();
+        let filter = self.filter.to_lowercase();
+        let mut visible = (0..self.entries.len())
+            .filter(|index| {
+                let entry = &self.entries[*index];
+                entry.detector.to_lowercase().contains(&filter)
+                    || entry.name.to_lowercase().contains(&filter)
+            })
+            .collect::<Vec<_>>();
+        visible.sort_by_cached_key(|index| {
+            let entry = &self.entries[*index];
+            (
+                self.group_by.key(entry),
+                entry.file_path.clone(),
+                entry.line,
+                entry.column,
+            )
+        });
+
+        self.rows.clear();
+        let mut group: Option<(usize, String)> = None;
+        for index in visible {
+            let (_, title) = self.group_by.key(&self.entries[index]);
+            if group.as_ref().is_none_or(|(_, current)| *current != title) {
+                group = Some((self.rows.len(), title.clone()));
+                self.rows.push(ListRow::Group { title, count: 0 });
+            }
+            if let Some((row, _)) = &group
+                && let ListRow::Group { count, .. } = &mut self.rows[*row]
+            {
+                *count += 1;
+            }
+            self.rows.push(ListRow::Finding(index));
+        }
+
+        self.selected = self
+            .rows
+            .iter()
+            .position(|row| matches!(row, ListRow::Finding(index) if Some(*index) == previous))
+            .or_else(|| {
+                self.rows
+                    .iter()
+                    .position(|row| matches!(row, ListRow::Finding(_)))
+            });
+        self.detail_scroll = 0;
+    }
+
+    // Moves over findings, skipping the group titles.
+    fn move_selection(&mut self, steps: isize) {
+        let Some(mut selected) = self.selected else {
+            return;
+        };
+        let mut row = selected;
+        let mut remaining = steps.unsigned_abs();
+        while remaining > 0 {
+            row = match (steps > 0).then(|| row + 1).or_else(|| row.checked_sub(1)) {
+                Some(next) if next < self.rows.len() => next,
+                _ => break,
+            };
+            if matches!(self.rows[row], ListRow::Finding(_)) {
+                selected = row;
+                remaining -= 1;
+            }
+        }
+        if self.selected != Some(selected) {
+            self.selected = Some(selected);
+            self.detail_scroll = 0;
+        }
+    }
+
+    fn set_triage(&mut self, status: Option<TriageStatus>) {
+        let Some(index) = self.selected_index() else {
+            return;
+        };
+        self.status = match self.save_triage(index, status) {
+            Ok(message) => message,
+            Err(error) => format!("Failed to save the triage file: {:#}", error),
+        };
+    }
+
+    fn save_triage(&mut self, index: usize, status: Option<TriageStatus>) -> Result<String> {
+        let fingerprint = self.entries[index].fingerprint.clone();
+        match status {
+            Some(status) => self.triage.mark(TriageEntry {
+                fingerprint: fingerprint.clone(),
+                status,
+                author: default_author(),
+                comment: String::new(),
+                date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            })?,
+            None if !self.triage.unmark(&fingerprint) => {
+                return Ok("The finding has not been triaged.".to_string());
+            }
+            None => {}
+        }
+        let path = self.triage.save(&self.workspace_root)?;
+
+        // Duplicated findings share the fingerprint
+        for entry in self
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.fingerprint == fingerprint)
+        {
+            entry.triage = status;
+        }
+        Ok(match status {
+            Some(status) => format!("Marked as {} in {}.", status, path.display()),
+            None => format!("Removed from {}.", path.display()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{App, GroupBy, ListRow};
+    use crate::{
+        scout::{core::triage::TriageStore, finding::Finding},
+        util::detectors_info::{LintInfo, LintStore},
+    };
+    use ratatui::crossterm::event::{KeyCode, KeyEvent};
+    use serde_json::json;
+    use std::path::Path;
+
+    fn finding(detector: &str, package: &str, line: u64) -> Finding {
+        Finding::new(json!({
+            "reason": "compiler-message",
+            "target": { "name": package },
+            "message": {
+                "code": { "code": detector },
+                "message": "message",
+                "rendered": "\u{1b}[1mwarning\u{1b}[0m: message",
+                "spans": [{
+                    "file_name": format!("{}/src/lib.rs", package),
+                    "line_start": line,
+                    "column_start": 5,
+                }],
+            },
+        }))
+    }
+
+    fn lint(id: &str, severity: &str, class: &str) -> LintInfo {
+        LintInfo {
+            id: id.to_string(),
+            name: id.to_string(),
+            severity: severity.to_string(),
+            vulnerability_class: class.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn app() -> App {
+        let mut detectors = LintStore::new();
+        detectors.insert(lint(
+            "unsafe_unwrap",
+            "Medium",
+            "Validations and error handling",
+        ));
+        detectors.insert(lint("set_contract_storage", "Critical", "Authorization"));
+        let findings = [
+            finding("unsafe_unwrap", "vault", 20),
+            finding("set_contract_storage", "token", 7),
+            finding("unsafe_unwrap", "token", 3),
+        ];
+        App::new(
+            &findings,
+            &detectors,
+            TriageStore::default(),
+            Path::new("/nonexistent"),
+        )
+    }
+
+    fn titles(app: &App) -> Vec<String> {
+        app.rows
+            .iter()
+            .filter_map(|row| match row {
+                ListRow::Group { title, count } => Some(format!("{} ({})", title, count)),
+                ListRow::Finding(_) => None,
+            })
+            .collect()
+    }
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::from(code));
+    }
+
+    #[test]
+    fn groups_findings() {
+        let mut app = app();
+        assert_eq!(app.group_by, GroupBy::Category);
+        assert_eq!(
+            titles(&app),
+            ["Authorization (1)", "Validations and error handling (2)"]
+        );
+        assert_eq!(app.entries[0].rendered, "warning: message");
+
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(titles(&app), ["Critical (1)", "Medium (2)"]);
+        press(&mut app, KeyCode::Char('g'));
+        assert_eq!(titles(&app), ["token (2)", "vault (1)"]);
+    }
+
+    #[test]
+    fn moves_over_findings_and_filters_by_detector() {
+        let mut app = app();
+        // The first row is a group title
+        assert_eq!(app.selected, Some(1));
+        press(&mut app, KeyCode::Down);
+        assert_eq!(app.selected, Some(3));
+        assert_eq!(app.selected_entry().unwrap().line, 3);
+        press(&mut app, KeyCode::End);
+        assert_eq!(app.selected_entry().unwrap().package, "vault");
+        press(&mut app, KeyCode::Home);
+        assert_eq!(app.selected, Some(1));
+
+        press(&mut app, KeyCode::Char('/'));
+        for c in "unwrap".chars() {
+            press(&mut app, KeyCode::Char(c));
+        }
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.visible_count(), 2);
+        assert_eq!(app.selected_entry().unwrap().detector, "unsafe_unwrap");
+
+        press(&mut app, KeyCode::Esc);
+        assert_eq!(app.visible_count(), 3);
+    }
+}
@@ -0,0 +1,93 @@
+use super::{
+    app::{Action, App},
+    ui,
+};
+use crate::{
+    scout::{core::triage::TriageStore, finding::Finding},
+    util::detectors_info::LintStore,
+};
+use anyhow::{Context, Result, bail};
+use ratatui::{
+    DefaultTerminal,
+    crossterm::{
+        event::{self, Event, KeyEventKind},
+        execute,
+        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    },
+    widgets::ListState,
+};
+use std::{
+    io::{IsTerminal, stdout},
+    path::Path,
+    process::Command,
+};
+
+// Runs `--tui` until the user quits. Statuses set with the triage keys are
+// saved right away.
+pub fn browse_findings(
+    findings: &[Finding],
+    detectors_info: &LintStore,
+    workspace_root: &Path,
+) -> Result<()> {
+    if !stdout().is_terminal() {
+        bail!("--tui needs an interactive terminal");
+    }
+    let triage = TriageStore::load(workspace_root)?;
+    let mut app = App::new(findings, detectors_info, triage, workspace_root);
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    let mut list_state = ListState::default();
+    loop {
+        terminal.draw(|frame| ui::draw(frame, app, &mut list_state))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        // Some terminals also report key releases
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match app.handle_key(key) {
+            Action::None => {}
+            Action::Quit => return Ok(()),
+            Action::OpenEditor(path, line) => {
+                disable_raw_mode()?;
+                execute!(stdout(), LeaveAlternateScreen)?;
+                let result = open_in_editor(&path, line);
+                enable_raw_mode()?;
+                execute!(stdout(), EnterAlternateScreen)?;
+                terminal.clear()?;
+                if let Err(error) = result {
+                    app.status = format!("{:#}", error);
+                }
+            }
+        }
+    }
+}
+
+// Opens `$VISUAL` or `$EDITOR` as `<editor> +<line> <file>`, which vi, nano,
+// emacs and helix understand.
+fn open_in_editor(path: &Path, line: u64) -> Result<()> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().context("No editor configured")?;
+    let status = Command::new(program)
+        .args(parts)
+        .arg(format!("+{}", line))
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if !status.success() {
+        bail!("{} exited with {}", program, status);
+    }
+    Ok(())
+}
@@ -0,0 +1,4 @@
+mod app;
+pub mod lib;
+mod ui;
+pub use lib::browse_findings;
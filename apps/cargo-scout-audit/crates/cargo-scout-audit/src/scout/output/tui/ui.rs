@@ -0,0 +1,139 @@
+use super::app::{App, Entry, ListRow};
+use crate::scout::output::report::Severity;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+const HELP: &str =
+    "↑/↓ move  g group  / filter  f/a/w triage  u untriage  e open  J/K scroll  q quit";
+
+pub fn draw(frame: &mut Frame, app: &App, list_state: &mut ListState) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+    let [list, detail] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main);
+
+    draw_list(frame, app, list, list_state);
+    draw_detail(frame, app, detail);
+    draw_footer(frame, app, footer);
+}
+
+fn draw_list(frame: &mut Frame, app: &App, area: Rect, list_state: &mut ListState) {
+    let items = app.rows.iter().map(|row| match row {
+        ListRow::Group { title, count } => {
+            ListItem::new(Line::from(format!("{} ({})", title, count)).bold().cyan())
+        }
+        ListRow::Finding(index) => {
+            let entry = &app.entries[*index];
+            let mut spans = vec![
+                Span::styled("  ● ", severity_style(entry.severity)),
+                Span::raw(format!(
+                    "{} {}:{}",
+                    entry.detector, entry.file_path, entry.line
+                )),
+            ];
+            if let Some(status) = entry.triage {
+                spans.push(Span::styled(
+                    format!(" [{}]", status),
+                    Style::new().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        }
+    });
+
+    let title = format!(
+        " Findings ({}) by {} ",
+        app.visible_count(),
+        app.group_by.label()
+    );
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+    list_state.select(app.selected);
+    frame.render_stateful_widget(list, area, list_state);
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title(" Details ");
+    let Some(entry) = app.selected_entry() else {
+        let message = if app.filter.is_empty() {
+            "No findings."
+        } else {
+            "No findings match the filter."
+        };
+        frame.render_widget(Paragraph::new(message).block(block), area);
+        return;
+    };
+
+    let paragraph = Paragraph::new(detail_lines(entry))
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_scroll, 0));
+    frame.render_widget(paragraph, area);
+}
+
+fn detail_lines(entry: &Entry) -> Vec<Line<'_>> {
+    let severity = entry
+        .severity
+        .map_or("Unknown", |severity| severity.as_lint_severity());
+    let mut lines = vec![
+        Line::from(entry.name.as_str()).bold(),
+        field("Detector", entry.detector.clone()),
+        Line::from(vec![
+            Span::raw("Severity: "),
+            Span::styled(severity, severity_style(entry.severity)),
+        ]),
+        field("Category", entry.category.clone()),
+        field("Package", entry.package.clone()),
+        field(
+            "Location",
+            format!("{}:{}:{}", entry.file_path, entry.line, entry.column),
+        ),
+        field("Fingerprint", entry.fingerprint.clone()),
+    ];
+    if let Some(status) = entry.triage {
+        lines.push(field("Triage", status.to_string()));
+    }
+
+    lines.push(Line::default());
+    lines.extend(entry.rendered.lines().map(Line::from));
+    if !entry.snippet.is_empty() {
+        lines.push(Line::default());
+        lines.push(Line::from("Snippet").bold());
+        lines.extend(entry.snippet.lines().map(|line| Line::from(line).green()));
+    }
+    lines
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let line = if app.editing_filter {
+        Line::from(format!("Filter by detector: {}_", app.filter))
+    } else if !app.status.is_empty() {
+        Line::from(app.status.as_str()).yellow()
+    } else if !app.filter.is_empty() {
+        Line::from(format!("[filter: {}, Esc to clear]  {}", app.filter, HELP)).dark_gray()
+    } else {
+        Line::from(HELP).dark_gray()
+    };
+    frame.render_widget(Paragraph::new(line), area);
+}
+
+fn field(label: &str, value: String) -> Line<'static> {
+    Line::from(vec![Span::raw(format!("{}: ", label)), Span::raw(value)])
+}
+
+fn severity_style(severity: Option<Severity>) -> Style {
+    let color = match severity {
+        Some(Severity::Critical) => Color::Red,
+        Some(Severity::Medium) => Color::Yellow,
+        Some(Severity::Minor) => Color::Green,
+        Some(Severity::Enhancement) => Color::Blue,
+        None => Color::Gray,
+    };
+    Style::new().fg(color)
+}
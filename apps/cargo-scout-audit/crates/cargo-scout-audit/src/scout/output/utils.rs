@@ -1,7 +1,7 @@
 use std::{
     fs::{self, File},
     io::{self, Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     str::FromStr,
 };
 
@@ -69,3 +69,34 @@ pub fn get_template<F: FnOnce() -> (String, String)>(get_path: F, template: &str
     }
     ret
 }
+
+// Reads the files below a custom template directory with one of the given
+// extensions, named by their path relative to it (`partials/header.html`).
+pub fn read_custom_templates(
+    directory: &Path,
+    extensions: &[&str],
+) -> io::Result<Vec<(String, String)>> {
+    let mut ret = Vec::new();
+    let mut pending = vec![directory.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                pending.push(path);
+                continue;
+            }
+            let extension = path.extension().and_then(|x| x.to_str()).unwrap_or("");
+            if !extensions.contains(&extension) {
+                continue;
+            }
+            let name = path
+                .strip_prefix(directory)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            ret.push((name, fs::read_to_string(&path)?));
+        }
+    }
+    ret.sort();
+    Ok(ret)
+}
@@ -43,6 +43,10 @@ impl LintStore {
         self.lints.get(id)
     }
 
+    pub fn find_by_id_mut(&mut self, id: &str) -> Option<&mut LintInfo> {
+        self.lints.get_mut(id)
+    }
+
     pub fn insert(&mut self, lint: LintInfo) -> Option<LintInfo> {
         self.lints.insert(lint.id.clone(), lint)
     }
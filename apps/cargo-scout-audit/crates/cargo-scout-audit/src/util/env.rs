@@ -26,4 +26,5 @@ declare_const!(RUSTFLAGS);
 declare_const!(RUSTUP_HOME);
 declare_const!(RUSTUP_TOOLCHAIN);
 declare_const!(RUST_BACKTRACE);
+declare_const!(SCOUT_NO_TELEMETRY);
 declare_const!(TARGET);
@@ -1,12 +1,75 @@
 use anyhow::Error;
 use colored::Colorize;
+use regex::Regex;
+use std::{
+    cell::Cell,
+    sync::{
+        LazyLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+static ANSI_ESCAPES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*m").unwrap());
+
+// Set while stdout carries the `--events` stream.
+static STDOUT_RESERVED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Set while this thread runs scout with `Scout::quiet`, i.e. embedded
+    // through the library API.
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Removes the colors of text rendered for the console, e.g. rustc diagnostics.
+pub fn strip_ansi(text: &str) -> String {
+    ANSI_ESCAPES.replace_all(text, "").into_owned()
+}
+
+pub fn reserve_stdout() {
+    STDOUT_RESERVED.store(true, Ordering::Relaxed);
+}
+
+// Silences the print helpers on the current thread until the guard is
+// dropped, which restores the previous state even on early returns.
+#[must_use]
+pub struct QuietGuard {
+    previous: bool,
+}
+
+pub fn quiet_scope(quiet: bool) -> QuietGuard {
+    QuietGuard {
+        previous: QUIET.replace(quiet),
+    }
+}
+
+impl Drop for QuietGuard {
+    fn drop(&mut self) {
+        QUIET.set(self.previous);
+    }
+}
+
+pub fn is_quiet() -> bool {
+    QUIET.get()
+}
+
+// Prints to stdout, or to stderr once stdout is reserved.
+pub fn print_line(line: &str) {
+    if is_quiet() {
+        return;
+    }
+    if STDOUT_RESERVED.load(Ordering::Relaxed) {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
 
 pub fn print_warning(message: &str) {
-    println!("{}", pretty_warning(message));
+    print_line(&pretty_warning(message));
 }
 
 pub fn print_error(message: &str) {
-    println!("{}", pretty_error(message));
+    print_line(&pretty_error(message));
 }
 
 pub fn print_full_error(e: &Error) {
@@ -16,7 +79,9 @@ pub fn print_full_error(e: &Error) {
 }
 
 pub fn print_info(message: &str) {
-    eprintln!("{}", pretty_info(message));
+    if !is_quiet() {
+        eprintln!("{}", pretty_info(message));
+    }
 }
 
 pub fn pretty_warning(message: &str) -> String {
@@ -30,3 +95,29 @@ pub fn pretty_error(message: &str) -> String {
 pub fn pretty_info(message: &str) -> String {
     format!("{} {}", "[INFO]".blue(), message)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{is_quiet, quiet_scope};
+
+    #[test]
+    fn quiet_scopes_restore_the_previous_state() {
+        {
+            let _outer = quiet_scope(true);
+            {
+                let _inner = quiet_scope(false);
+                assert!(!is_quiet());
+            }
+            assert!(is_quiet());
+        }
+        assert!(!is_quiet());
+        assert!(
+            !std::thread::spawn(|| {
+                let _quiet = quiet_scope(true);
+                std::thread::spawn(is_quiet).join().unwrap()
+            })
+            .join()
+            .unwrap()
+        );
+    }
+}
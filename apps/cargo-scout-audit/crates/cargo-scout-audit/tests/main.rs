@@ -6,8 +6,10 @@ mod tests {
 
     use anyhow::Result;
     use cargo_scout_audit::{
+        ScoutBuilder,
         cli_args::{OutputFormat, Scout},
         run::run_scout,
+        scout::output::report::Severity,
     };
     use lazy_static::lazy_static;
     use once_cell::sync::Lazy;
@@ -187,12 +189,68 @@ mod tests {
         test_output_fn("report.sarif", OutputFormat::Sarif)
     }
 
+    #[test]
+    fn test_junit_format() -> Result<()> {
+        test_output_fn("report.xml", OutputFormat::Junit)
+    }
+
+    #[test]
+    fn test_gitlab_code_quality_format() -> Result<()> {
+        test_output_fn(
+            "gl-code-quality-report.json",
+            OutputFormat::GitlabCodeQuality,
+        )
+    }
+
+    #[test]
+    fn test_sonar_generic_format() -> Result<()> {
+        test_output_fn("sonar-issues.json", OutputFormat::SonarGeneric)
+    }
+
     #[test]
     #[ignore]
     fn test_pdf_format() -> Result<()> {
         test_output_fn("report.pdf", OutputFormat::Pdf)
     }
 
+    #[test]
+    fn test_output_dir() -> Result<()> {
+        // Given
+        let output_dir = TEST_DIR.path().join(Uuid::new_v4().to_string());
+        let formats = vec![
+            OutputFormat::Json,
+            OutputFormat::RawJson,
+            OutputFormat::RawSingleJson,
+            OutputFormat::UnfilteredJson,
+        ];
+        let scout_opts = Scout {
+            manifest_path: Some(get_test_cases().first().unwrap().clone()),
+            output_format: formats.clone(),
+            output_dir: Some(output_dir.clone()),
+            local_detectors: Some(DETECTORS_DIR.clone()),
+            scout_source: Some(SCOUT_SOURCE.clone()),
+            ..Scout::default()
+        };
+
+        // When
+        let result = run_scout(scout_opts);
+
+        // Then
+        assert!(result.is_ok(), "Scout should run");
+        for format in formats.iter() {
+            assert!(output_dir.join(format.file_name()).is_file());
+        }
+        let index: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(output_dir.join("index.json"))?)?;
+        assert_eq!(
+            index["outputs"].as_array().map(Vec::len),
+            Some(formats.len())
+        );
+
+        let _ = fs::remove_dir_all(&output_dir);
+        Ok(())
+    }
+
     fn test_output_format(output_file: &str, format: &OutputFormat) -> Result<()> {
         // For debugging purposes
         let output_format = format.clone();
@@ -241,6 +299,12 @@ mod tests {
         );
 
         if format == &OutputFormat::Pdf {
+            let contents = fs::read(output_file)?;
+            assert!(
+                contents.starts_with(b"%PDF-"),
+                "[{:?}] File should be a PDF document",
+                output_format
+            );
             return Ok(());
         }
         // Read file contents
@@ -302,6 +366,70 @@ mod tests {
         check_counts(&counts, &expected);
     }
 
+    #[test]
+    fn test_scout_with_fail_on() {
+        // Given
+        let contract_path = get_test_cases()
+            .iter()
+            .find(|y| y.to_str().unwrap().contains("soroban"))
+            .unwrap()
+            .clone();
+        let cicd_dir = TEST_DIR.path().join(Uuid::new_v4().to_string());
+        fs::create_dir_all(&cicd_dir).unwrap();
+
+        // When
+        let scout_opts = Scout {
+            manifest_path: Some(contract_path),
+            local_detectors: Some(DETECTORS_DIR.clone()),
+            scout_source: Some(SCOUT_SOURCE.clone()),
+            cicd: Some(cicd_dir.clone()),
+            fail_on: Some(Severity::Enhancement),
+            ..Scout::default()
+        };
+        let result = run_scout(scout_opts);
+
+        // Then
+        assert!(result.is_ok(), "Scout should run");
+        let result = result.unwrap();
+        assert!(
+            result.threshold_exceeded,
+            "Findings should exceed the threshold"
+        );
+        assert_eq!(result.exit_code(), 2);
+        assert!(cicd_dir.join("FAIL").is_file(), "FAIL file should be kept");
+    }
+
+    #[test]
+    fn test_library_api() {
+        // Given
+        let contract_path = get_test_cases()
+            .iter()
+            .find(|y| y.to_str().unwrap().contains("soroban"))
+            .unwrap()
+            .clone();
+
+        // When
+        let result = ScoutBuilder::new()
+            .manifest_path(contract_path)
+            .local_detectors(DETECTORS_DIR.clone())
+            .scout_source(SCOUT_SOURCE.clone())
+            .fail_on(Severity::Enhancement)
+            .run();
+
+        // Then
+        assert!(result.is_ok(), "Scout should run");
+        let result = result.unwrap();
+        assert!(
+            !result.report.findings.is_empty(),
+            "Report should have findings"
+        );
+        assert!(result.failed_crates().is_empty(), "Crates should build");
+        assert!(
+            result.threshold_exceeded,
+            "Findings should exceed the threshold"
+        );
+    }
+
     #[test]
     fn test_message_format() {
         let path = "tests/contracts/substrate-pallets/";
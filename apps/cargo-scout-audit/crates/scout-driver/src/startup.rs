@@ -1,5 +1,9 @@
 use anyhow::{Context, Ok, Result};
-use cargo_scout_audit::{cli_args::Scout, scout::finding::Finding, util::print::print_info};
+use cargo_scout_audit::{
+    cli_args::Scout,
+    scout::finding::Finding,
+    util::print::{print_info, quiet_scope},
+};
 use dylint::opts::{Check, Dylint, LibrarySelection, Operation};
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
@@ -64,6 +68,7 @@ pub fn run_dylint(
     opts: &Scout,
     inside_vscode: bool,
 ) -> Result<(bool, NamedTempFile)> {
+    let _quiet = quiet_scope(opts.quiet);
     print_info("Running scout...");
 
     // Convert detectors paths to string
@@ -101,7 +106,7 @@ pub fn run_dylint(
 
     let options = Dylint {
         pipe_stdout,
-        quiet: opts.verbose,
+        quiet: opts.verbose || opts.quiet,
         operation: Operation::Check(check_opts.clone()),
         ..Default::default()
     };
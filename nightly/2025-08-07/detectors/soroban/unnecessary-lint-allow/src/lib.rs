@@ -44,7 +44,8 @@ impl UnnecessaryLintAllow {
         let mut stack = VecDeque::from([tokens]);
 
         while let Some(current_stream) = stack.pop_front() {
-            for tree in current_stream.iter() {
+            let mut trees = current_stream.iter().peekable();
+            while let Some(tree) = trees.next() {
                 match tree {
                     TokenTree::Token(
                         Token {
@@ -53,7 +54,20 @@ impl UnnecessaryLintAllow {
                         },
                         _,
                     ) => {
-                        lint_names.push(ident.to_string());
+                        // `reason = "..."` is a justification, not a detector
+                        let is_key = matches!(
+                            trees.peek(),
+                            Some(TokenTree::Token(
+                                Token {
+                                    kind: TokenKind::Eq,
+                                    ..
+                                },
+                                _
+                            ))
+                        );
+                        if !is_key {
+                            lint_names.push(ident.to_string());
+                        }
                     }
                     TokenTree::Delimited(_, _, Delimiter::Parenthesis, inner_stream) => {
                         stack.push_back(inner_stream);
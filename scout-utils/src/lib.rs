@@ -1,6 +1,53 @@
 use proc_macro::TokenStream;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input,
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+// `scout_allow(detector, ..., reason = "...")`. Scout reads the attribute from
+// the sources, so the macro only checks that it is well formed.
+struct ScoutAllowArgs;
+
+impl Parse for ScoutAllowArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut detectors = 0;
+        let mut reason = false;
+        for argument in Punctuated::<ScoutAllowArg, Token![,]>::parse_terminated(input)? {
+            match argument {
+                ScoutAllowArg::Detector => detectors += 1,
+                ScoutAllowArg::Reason(literal) if reason => {
+                    return Err(syn::Error::new(literal.span(), "duplicate `reason`"));
+                }
+                ScoutAllowArg::Reason(_) => reason = true,
+            }
+        }
+        if detectors == 0 {
+            return Err(input.error("expected at least one detector name"));
+        }
+        Ok(ScoutAllowArgs)
+    }
+}
+
+enum ScoutAllowArg {
+    Detector,
+    Reason(LitStr),
+}
+
+impl Parse for ScoutAllowArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident: Ident = input.parse()?;
+        if ident == "reason" && input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            return Ok(ScoutAllowArg::Reason(input.parse()?));
+        }
+        Ok(ScoutAllowArg::Detector)
+    }
+}
 
 #[proc_macro_attribute]
-pub fn scout_allow(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn scout_allow(attr: TokenStream, item: TokenStream) -> TokenStream {
+    parse_macro_input!(attr as ScoutAllowArgs);
     item
 }